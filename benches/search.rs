@@ -0,0 +1,88 @@
+//! Query latency against a synthetic catalog of 5,000 concepts, built from
+//! recombined fragments of the real one so token statistics stay realistic.
+//!
+//! Run with `cargo bench --bench search`.
+
+use ckad_wiki::ckad_data::{catalog_concepts, Category, Concept};
+use ckad_wiki::search::{Document, SearchIndex, SearchQuery};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const CONCEPT_COUNT: usize = 5_000;
+const CATEGORY_COUNT: usize = 50;
+
+const QUERIES: &[&str] = &[
+    "rollout",
+    "rolback",
+    "svc nodeport",
+    "cat:category-7 kind:yaml probe -helm",
+    "\"dry-run=client\"",
+];
+
+/// Deterministic synthetic catalog: every concept mixes the title, command and
+/// description of different real concepts, plus a unique ordinal token. The
+/// catalog is leaked to get the same `'static` data the real one uses.
+fn synthetic_catalog() -> &'static [Category] {
+    let real: Vec<&Concept> = catalog_concepts().map(|(_, concept)| concept).collect();
+
+    let mut concepts: Vec<Vec<Concept>> = (0..CATEGORY_COUNT).map(|_| Vec::new()).collect();
+    for ordinal in 0..CONCEPT_COUNT {
+        let title_source = real[ordinal % real.len()];
+        let command_source = real[(ordinal * 7 + 3) % real.len()];
+        let description_source = real[(ordinal * 13 + 5) % real.len()];
+
+        concepts[ordinal % CATEGORY_COUNT].push(Concept {
+            id: format!("synthetic-{ordinal}").leak(),
+            title: format!("{} {ordinal}", title_source.title).leak(),
+            command: command_source.command,
+            description: description_source.description,
+            kind: command_source.kind,
+            placeholders: command_source.placeholders,
+            equivalents: command_source.equivalents,
+            fragment: command_source.fragment,
+        });
+    }
+
+    concepts
+        .into_iter()
+        .enumerate()
+        .map(|(index, concepts)| Category {
+            id: format!("category-{index}").leak(),
+            name: format!("Synthetic Category {index}").leak(),
+            concepts: concepts.leak(),
+        })
+        .collect::<Vec<_>>()
+        .leak()
+}
+
+fn bench_search(c: &mut Criterion) {
+    let categories = synthetic_catalog();
+    let category_refs: Vec<&Category> = categories.iter().collect();
+    let concepts: Vec<(&str, &Concept)> = categories
+        .iter()
+        .flat_map(|category| category.concepts.iter().map(move |concept| (category.id, concept)))
+        .collect();
+
+    c.bench_function("build index (5000 concepts)", |b| {
+        b.iter(|| SearchIndex::build(concepts.iter().map(|(_, concept)| *concept)))
+    });
+
+    let index = SearchIndex::build(concepts.iter().map(|(_, concept)| *concept));
+    assert_eq!(index.concept_count(), CONCEPT_COUNT);
+
+    let mut group = c.benchmark_group("query (5000 concepts)");
+    for text in QUERIES {
+        group.bench_function(*text, |b| {
+            b.iter(|| {
+                let query = SearchQuery::parse(black_box(text), &category_refs);
+                query.search(&[&index], |ordinal| {
+                    let (category_id, concept) = concepts[ordinal];
+                    Document { category_id, concept, note: None }
+                })
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_search);
+criterion_main!(benches);
@@ -0,0 +1,510 @@
+//! Compiles the concept catalog under `data/` into static Rust data.
+//!
+//! `data/catalog.toml` lists the category files (in display order) and the
+//! schema version; every file under `data/categories/` holds one category.
+//! A category's id is its file stem, and every concept declares its own `id`,
+//! so both stay stable when titles are reworded. A concept's `kind` (shell or
+//! yaml) is inferred from its command unless declared explicitly; its
+//! `placeholders` must each occur in the command as a whole word, and its
+//! `equivalents` list other answers the typing drill accepts. A YAML snippet
+//! that is only part of an object names the schema type it belongs to in
+//! `fragment` (see `catalog_rules::FRAGMENT_TYPES`). Any
+//! malformed or invalid entry fails the build with `file:line: message`.
+//!
+//! `data/exam-tasks.toml` holds the mock exam tasks. Each belongs to a
+//! curriculum domain and links to the concepts that solve it; they are
+//! compiled into `catalog.rs` next to the categories.
+//!
+//! Besides `catalog.rs`, this emits `search_index.rs`: the inverted index used
+//! by the search, built with the same tokenizer as the runtime.
+
+#[path = "src/search/tokens.rs"]
+#[allow(dead_code)]
+mod tokens;
+
+#[path = "src/placeholders.rs"]
+#[allow(dead_code)]
+mod placeholders;
+
+#[path = "src/domains.rs"]
+#[allow(dead_code)]
+mod domains;
+
+#[path = "src/catalog_rules.rs"]
+#[allow(dead_code)]
+mod catalog_rules;
+
+use catalog_rules::{check_text as text_problem, is_placeholder_name, is_slug, looks_like_yaml, FRAGMENT_TYPES};
+use domains::Domain;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fmt::{self, Write as _};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use toml::Spanned;
+
+const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Manifest {
+    version: Spanned<u32>,
+    categories: Vec<Spanned<String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CategoryFile {
+    name: Spanned<String>,
+    concepts: Spanned<Vec<ConceptEntry>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConceptEntry {
+    id: Spanned<String>,
+    title: Spanned<String>,
+    command: Spanned<String>,
+    description: Option<Spanned<String>>,
+    kind: Option<Spanned<String>>,
+    placeholders: Option<Vec<Spanned<String>>>,
+    equivalents: Option<Vec<Spanned<String>>>,
+    fragment: Option<Spanned<String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExamTaskFile {
+    tasks: Vec<ExamTaskEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExamTaskEntry {
+    id: Spanned<String>,
+    domain: Spanned<String>,
+    points: Spanned<u32>,
+    title: Spanned<String>,
+    task: Spanned<String>,
+    concepts: Spanned<Vec<Spanned<String>>>,
+}
+
+/// Points a single exam task may be worth.
+const TASK_POINTS: std::ops::RangeInclusive<u32> = 1..=10;
+
+struct CatalogError {
+    path: PathBuf,
+    line: usize,
+    message: String,
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.path.display(), self.line, self.message)
+    }
+}
+
+/// A parsed TOML file, kept around so spans can be turned into line numbers.
+struct Source {
+    path: PathBuf,
+    text: String,
+}
+
+impl Source {
+    fn read(path: PathBuf) -> Result<Self, CatalogError> {
+        match fs::read_to_string(&path) {
+            Ok(text) => Ok(Source { path, text }),
+            Err(err) => Err(CatalogError {
+                path,
+                line: 1,
+                message: format!("cannot read file: {err}"),
+            }),
+        }
+    }
+
+    fn parse<T: for<'de> Deserialize<'de>>(&self) -> Result<T, CatalogError> {
+        toml::from_str(&self.text).map_err(|err| {
+            let offset = err.span().map(|span| span.start).unwrap_or(0);
+            self.error_at(offset, err.message())
+        })
+    }
+
+    fn error_at(&self, offset: usize, message: impl Into<String>) -> CatalogError {
+        let line = self.text[..offset.min(self.text.len())].matches('\n').count() + 1;
+        CatalogError {
+            path: self.path.clone(),
+            line,
+            message: message.into(),
+        }
+    }
+
+    fn error<T>(&self, value: &Spanned<T>, message: impl Into<String>) -> CatalogError {
+        self.error_at(value.span().start, message)
+    }
+}
+
+fn main() {
+    let root = PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR"));
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").expect("OUT_DIR"));
+
+    println!("cargo:rerun-if-changed=data");
+
+    let (catalog, index) = match compile_catalog(&root.join("data")) {
+        Ok(generated) => generated,
+        Err(err) => {
+            eprintln!("error: invalid concept catalog\n  --> {err}");
+            process::exit(1);
+        }
+    };
+
+    fs::write(out_dir.join("catalog.rs"), catalog).expect("failed to write catalog.rs");
+    fs::write(out_dir.join("search_index.rs"), index).expect("failed to write search_index.rs");
+}
+
+/// Returns the generated `catalog.rs` and `search_index.rs`.
+fn compile_catalog(data_dir: &Path) -> Result<(String, String), CatalogError> {
+    let manifest_source = Source::read(data_dir.join("catalog.toml"))?;
+    let manifest: Manifest = manifest_source.parse()?;
+
+    if *manifest.version.get_ref() != SCHEMA_VERSION {
+        return Err(manifest_source.error(
+            &manifest.version,
+            format!(
+                "unsupported catalog version {} (expected {SCHEMA_VERSION})",
+                manifest.version.get_ref()
+            ),
+        ));
+    }
+
+    let categories_dir = data_dir.join("categories");
+    let mut listed = HashSet::new();
+    let mut seen_ids = HashSet::new();
+    let mut seen_titles = HashSet::new();
+    let mut documents: Vec<(u32, u8, String)> = Vec::new();
+    let mut concept_count: u32 = 0;
+    let mut out = String::from("// @generated by build.rs from data/ - do not edit.\n\n");
+    out.push_str("static CATALOG: &[Category] = &[\n");
+
+    for file in &manifest.categories {
+        let stem = file.get_ref();
+        if !is_slug(stem) {
+            return Err(manifest_source.error(
+                file,
+                format!("category file `{stem}` must be lowercase letters, digits and dashes"),
+            ));
+        }
+        if !listed.insert(stem.clone()) {
+            return Err(manifest_source.error(file, format!("category file `{stem}` is listed twice")));
+        }
+
+        let path = categories_dir.join(format!("{stem}.toml"));
+        if !path.is_file() {
+            return Err(manifest_source.error(
+                file,
+                format!("category file `{}` does not exist", path.display()),
+            ));
+        }
+        println!("cargo:rerun-if-changed={}", path.display());
+
+        let source = Source::read(path)?;
+        let category: CategoryFile = source.parse()?;
+        check_text(&source, &category.name, "category name")?;
+        if category.concepts.get_ref().is_empty() {
+            return Err(source.error(&category.concepts, "category has no concepts"));
+        }
+
+        writeln!(out, "    Category {{").unwrap();
+        writeln!(out, "        id: {stem:?},").unwrap();
+        writeln!(out, "        name: {:?},", category.name.get_ref()).unwrap();
+        writeln!(out, "        concepts: &[").unwrap();
+
+        for concept in category.concepts.get_ref() {
+            if !is_slug(concept.id.get_ref()) {
+                return Err(source.error(
+                    &concept.id,
+                    format!(
+                        "concept id `{}` must be lowercase letters, digits and dashes",
+                        concept.id.get_ref()
+                    ),
+                ));
+            }
+            if !seen_ids.insert(concept.id.get_ref().clone()) {
+                return Err(source.error(
+                    &concept.id,
+                    format!("duplicate concept id `{}`", concept.id.get_ref()),
+                ));
+            }
+            check_text(&source, &concept.title, "concept title")?;
+            check_text(&source, &concept.command, "concept command")?;
+            if let Some(description) = &concept.description {
+                check_text(&source, description, "concept description")?;
+            }
+            if !seen_titles.insert(concept.title.get_ref().clone()) {
+                return Err(source.error(
+                    &concept.title,
+                    format!("duplicate concept title `{}`", concept.title.get_ref()),
+                ));
+            }
+
+            writeln!(out, "            Concept {{").unwrap();
+            writeln!(out, "                id: {:?},", concept.id.get_ref()).unwrap();
+            writeln!(out, "                title: {:?},", concept.title.get_ref()).unwrap();
+            writeln!(out, "                command: {:?},", concept.command.get_ref()).unwrap();
+            match &concept.description {
+                Some(description) => {
+                    writeln!(out, "                description: Some({:?}),", description.get_ref()).unwrap()
+                }
+                None => writeln!(out, "                description: None,").unwrap(),
+            }
+            let kind = match &concept.kind {
+                Some(kind) => match kind.get_ref().as_str() {
+                    "shell" => "Shell",
+                    "yaml" => "Yaml",
+                    other => {
+                        return Err(source.error(
+                            kind,
+                            format!("unknown concept kind `{other}` (expected `shell` or `yaml`)"),
+                        ))
+                    }
+                },
+                None if looks_like_yaml(concept.command.get_ref()) => "Yaml",
+                None => "Shell",
+            };
+            writeln!(out, "                kind: ConceptKind::{kind},").unwrap();
+            let placeholders = check_placeholders(&source, concept)?;
+            writeln!(out, "                placeholders: &{placeholders:?},").unwrap();
+            let equivalents = check_equivalents(&source, concept)?;
+            writeln!(out, "                equivalents: &{equivalents:?},").unwrap();
+            let fragment = check_fragment(&source, concept, kind)?;
+            writeln!(out, "                fragment: {fragment:?},").unwrap();
+            writeln!(out, "            }},").unwrap();
+
+            // Field slots follow `search::Field`: title, description, command.
+            let ordinal = concept_count;
+            concept_count += 1;
+            documents.push((ordinal, 0, concept.title.get_ref().clone()));
+            if let Some(description) = &concept.description {
+                documents.push((ordinal, 1, description.get_ref().clone()));
+            }
+            documents.push((ordinal, 2, concept.command.get_ref().clone()));
+        }
+
+        writeln!(out, "        ],").unwrap();
+        writeln!(out, "    }},").unwrap();
+    }
+
+    out.push_str("];\n");
+
+    check_unlisted_files(&categories_dir, &listed, &manifest_source)?;
+
+    out.push('\n');
+    out.push_str(&compile_exam_tasks(&data_dir.join("exam-tasks.toml"), &seen_ids)?);
+
+    let index = generate_index(concept_count, &documents);
+    Ok((out, index))
+}
+
+/// Exam tasks need a known domain, sensible points and at least one linked
+/// concept from the catalog; every domain needs at least one task.
+fn compile_exam_tasks(path: &Path, concept_ids: &HashSet<String>) -> Result<String, CatalogError> {
+    println!("cargo:rerun-if-changed={}", path.display());
+    let source = Source::read(path.to_path_buf())?;
+    let file: ExamTaskFile = source.parse()?;
+
+    let mut seen_ids = HashSet::new();
+    let mut covered = HashSet::new();
+    let mut out = String::from("static EXAM_TASKS: &[ExamTask] = &[\n");
+
+    for task in &file.tasks {
+        let id = task.id.get_ref();
+        if !is_slug(id) {
+            return Err(source.error(
+                &task.id,
+                format!("task id `{id}` must be lowercase letters, digits and dashes"),
+            ));
+        }
+        if !seen_ids.insert(id.clone()) {
+            return Err(source.error(&task.id, format!("duplicate task id `{id}`")));
+        }
+        let Some(domain) = Domain::from_slug(task.domain.get_ref()) else {
+            let known: Vec<_> = Domain::ALL.iter().map(|domain| domain.slug()).collect();
+            return Err(source.error(
+                &task.domain,
+                format!("unknown domain `{}` (expected one of {})", task.domain.get_ref(), known.join(", ")),
+            ));
+        };
+        covered.insert(domain);
+        if !TASK_POINTS.contains(task.points.get_ref()) {
+            return Err(source.error(
+                &task.points,
+                format!(
+                    "task is worth {} points (expected {} to {})",
+                    task.points.get_ref(),
+                    TASK_POINTS.start(),
+                    TASK_POINTS.end()
+                ),
+            ));
+        }
+        check_text(&source, &task.title, "task title")?;
+        check_text(&source, &task.task, "task text")?;
+        if task.concepts.get_ref().is_empty() {
+            return Err(source.error(&task.concepts, "task links no concepts"));
+        }
+        let mut concepts: Vec<&str> = Vec::new();
+        for concept in task.concepts.get_ref() {
+            let concept_id = concept.get_ref().as_str();
+            if !concept_ids.contains(concept_id) {
+                return Err(source.error(concept, format!("unknown concept id `{concept_id}`")));
+            }
+            if concepts.contains(&concept_id) {
+                return Err(source.error(concept, format!("concept `{concept_id}` is linked twice")));
+            }
+            concepts.push(concept_id);
+        }
+
+        writeln!(out, "    ExamTask {{").unwrap();
+        writeln!(out, "        id: {id:?},").unwrap();
+        writeln!(out, "        domain: Domain::{domain:?},").unwrap();
+        writeln!(out, "        points: {},", task.points.get_ref()).unwrap();
+        writeln!(out, "        title: {:?},", task.title.get_ref()).unwrap();
+        writeln!(out, "        task: {:?},", task.task.get_ref()).unwrap();
+        writeln!(out, "        concepts: &{concepts:?},").unwrap();
+        writeln!(out, "    }},").unwrap();
+    }
+
+    if let Some(missing) = Domain::ALL.into_iter().find(|domain| !covered.contains(domain)) {
+        return Err(source.error_at(0, format!("no exam task for domain `{}`", missing.slug())));
+    }
+
+    out.push_str("];\n");
+    Ok(out)
+}
+
+fn generate_index(concept_count: u32, documents: &[(u32, u8, String)]) -> String {
+    const FIELDS: [&str; 3] = ["Title", "Description", "Command"];
+
+    let inverted = tokens::invert(documents.iter().map(|(concept, slot, text)| (*concept, *slot, text.as_str())));
+
+    let mut out = String::from("// @generated by build.rs from data/ - do not edit.\n\n");
+    out.push_str("static CATALOG_INDEX: SearchIndex = SearchIndex {\n");
+    writeln!(out, "    concept_count: {concept_count},").unwrap();
+    out.push_str("    entries: Cow::Borrowed(&[\n");
+    for (token, postings) in inverted {
+        write!(out, "        IndexEntry {{ token: Cow::Borrowed({token:?}), postings: Cow::Borrowed(&[").unwrap();
+        for (concept, slot) in postings {
+            write!(out, "Posting {{ concept: {concept}, field: Field::{} }}, ", FIELDS[slot as usize]).unwrap();
+        }
+        out.push_str("]) },\n");
+    }
+    out.push_str("    ]),\n};\n");
+
+    out
+}
+
+/// Placeholder names must be well formed, unique within the concept, and
+/// actually appear in its command.
+fn check_placeholders<'a>(source: &Source, concept: &'a ConceptEntry) -> Result<Vec<&'a str>, CatalogError> {
+    let mut names: Vec<&str> = Vec::new();
+
+    for placeholder in concept.placeholders.iter().flatten() {
+        let name = placeholder.get_ref().as_str();
+        if !is_placeholder_name(name) {
+            return Err(source.error(
+                placeholder,
+                format!("placeholder `{name}` must be uppercase letters, digits and underscores"),
+            ));
+        }
+        if names.contains(&name) {
+            return Err(source.error(placeholder, format!("placeholder `{name}` is declared twice")));
+        }
+        if placeholders::occurrences(concept.command.get_ref(), name).is_empty() {
+            return Err(source.error(
+                placeholder,
+                format!("placeholder `{name}` does not appear in the command"),
+            ));
+        }
+        names.push(name);
+    }
+
+    Ok(names)
+}
+
+/// Equivalent answers must be well formed and differ from the command and
+/// from each other.
+fn check_equivalents<'a>(source: &Source, concept: &'a ConceptEntry) -> Result<Vec<&'a str>, CatalogError> {
+    let mut texts: Vec<&str> = Vec::new();
+
+    for equivalent in concept.equivalents.iter().flatten() {
+        check_text(source, equivalent, "equivalent answer")?;
+        let text = equivalent.get_ref().as_str();
+        if text == concept.command.get_ref() {
+            return Err(source.error(equivalent, "equivalent answer repeats the command"));
+        }
+        if texts.contains(&text) {
+            return Err(source.error(equivalent, format!("equivalent answer `{text}` is listed twice")));
+        }
+        texts.push(text);
+    }
+
+    Ok(texts)
+}
+
+/// Only YAML snippets can be fragments, and only of a type the schema knows.
+fn check_fragment<'a>(
+    source: &Source,
+    concept: &'a ConceptEntry,
+    kind: &str,
+) -> Result<Option<&'a str>, CatalogError> {
+    let Some(fragment) = &concept.fragment else {
+        return Ok(None);
+    };
+    if kind != "Yaml" {
+        return Err(source.error(fragment, "only YAML snippets can declare a `fragment` type"));
+    }
+    let name = fragment.get_ref().as_str();
+    if !FRAGMENT_TYPES.contains(&name) {
+        return Err(source.error(
+            fragment,
+            format!("unknown fragment type `{name}` (expected one of {})", FRAGMENT_TYPES.join(", ")),
+        ));
+    }
+    Ok(Some(name))
+}
+
+fn check_text(source: &Source, value: &Spanned<String>, what: &str) -> Result<(), CatalogError> {
+    match text_problem(value.get_ref()) {
+        Some(problem) => Err(source.error(value, format!("{what} {problem}"))),
+        None => Ok(()),
+    }
+}
+
+/// A category file that is not referenced from `catalog.toml` would be
+/// silently dropped, so treat it as an error.
+fn check_unlisted_files(
+    categories_dir: &Path,
+    listed: &HashSet<String>,
+    manifest_source: &Source,
+) -> Result<(), CatalogError> {
+    let entries = fs::read_dir(categories_dir).map_err(|err| CatalogError {
+        path: categories_dir.to_path_buf(),
+        line: 1,
+        message: format!("cannot read directory: {err}"),
+    })?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("toml") {
+            continue;
+        }
+        let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+        if !listed.contains(stem) {
+            return Err(manifest_source.error_at(
+                0,
+                format!("category file `{}` is not listed in `categories`", path.display()),
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+//! Rules every concept must follow, whether it comes from `data/` or was
+//! added in the browser. `build.rs` includes this file, so it must not depend
+//! on the rest of the crate.
+
+/// Ids end up in URLs (`#/concept/<id>`) and in saved browser state, so they
+/// are restricted to `a-z`, `0-9` and single inner dashes.
+pub fn is_slug(text: &str) -> bool {
+    !text.is_empty()
+        && text.split('-').all(|part| {
+            !part.is_empty() && part.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        })
+}
+
+/// Placeholder names are uppercase letters, digits and underscores, starting
+/// with a letter, so they cannot be confused with the commands around them.
+pub fn is_placeholder_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.bytes().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_')
+}
+
+/// Schema types a YAML fragment may declare in `fragment`: the building
+/// blocks of a Pod, or a kind when the snippet is part of such an object.
+pub const FRAGMENT_TYPES: &[&str] = &[
+    "container",
+    "podSpec",
+    "probe",
+    "volume",
+    "Pod",
+    "Deployment",
+    "Job",
+    "CronJob",
+    "Service",
+    "PersistentVolume",
+    "PersistentVolumeClaim",
+    "NetworkPolicy",
+    "Ingress",
+];
+
+/// Rejects empty values and stray surrounding whitespace, which would
+/// otherwise render as blank or misaligned cards. Returns what is wrong.
+pub fn check_text(text: &str) -> Option<&'static str> {
+    if text.trim().is_empty() {
+        Some("must not be empty")
+    } else if text.trim() != text {
+        Some("has leading or trailing whitespace")
+    } else {
+        None
+    }
+}
+
+/// Commands whose first meaningful line is a `key:` mapping entry (optionally
+/// a `- key:` list item) are YAML snippets; everything else is shell.
+pub fn looks_like_yaml(command: &str) -> bool {
+    let first_line = command
+        .lines()
+        .map(str::trim_start)
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .unwrap_or_default();
+    let entry = first_line.strip_prefix("- ").unwrap_or(first_line).trim_start();
+
+    entry.split_once(':').is_some_and(|(key, rest)| {
+        let mut chars = key.chars();
+        chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+            && (rest.is_empty() || rest.starts_with(' '))
+    })
+}
@@ -0,0 +1,79 @@
+use leptos::*;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+/// How a copy request ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CopyOutcome {
+    /// The text is on the clipboard.
+    Copied,
+    /// Copying was refused, but `select_target` is now selected so the
+    /// reader can press Ctrl+C themselves.
+    Selected,
+    Failed,
+}
+
+/// Copies `text`, trying in turn:
+///
+/// 1. the async Clipboard API, which needs a secure origin and may be denied
+///    by the browser or its policies;
+/// 2. a hidden textarea and `document.execCommand("copy")`, which older and
+///    locked-down browsers still allow;
+/// 3. selecting `select_target`, leaving the actual copy to the reader.
+pub async fn copy_text(text: &str, select_target: Option<web_sys::Element>) -> CopyOutcome {
+    if write_with_clipboard_api(text).await || write_with_exec_command(text) {
+        return CopyOutcome::Copied;
+    }
+
+    match select_target {
+        Some(element) if select_contents(&element) => CopyOutcome::Selected,
+        _ => CopyOutcome::Failed,
+    }
+}
+
+async fn write_with_clipboard_api(text: &str) -> bool {
+    let navigator = window().navigator();
+    // `navigator.clipboard` is undefined on insecure origins and in older
+    // browsers; calling into it would throw.
+    let has_clipboard = js_sys::Reflect::get(&navigator, &JsValue::from_str("clipboard"))
+        .is_ok_and(|clipboard| !clipboard.is_undefined() && !clipboard.is_null());
+    if !has_clipboard {
+        return false;
+    }
+
+    JsFuture::from(navigator.clipboard().write_text(text)).await.is_ok()
+}
+
+fn write_with_exec_command(text: &str) -> bool {
+    let document = document();
+    let (Some(body), Ok(html_document)) = (document.body(), document.clone().dyn_into::<web_sys::HtmlDocument>())
+    else {
+        return false;
+    };
+    let Some(textarea) = document
+        .create_element("textarea")
+        .ok()
+        .and_then(|element| element.dyn_into::<web_sys::HtmlTextAreaElement>().ok())
+    else {
+        return false;
+    };
+
+    textarea.set_value(text);
+    let _ = textarea.set_attribute("readonly", "");
+    let _ = textarea.set_attribute("style", "position: fixed; top: 0; left: 0; opacity: 0;");
+    if body.append_child(&textarea).is_err() {
+        return false;
+    }
+    textarea.select();
+    let copied = html_document.exec_command("copy").unwrap_or(false);
+    textarea.remove();
+
+    copied
+}
+
+fn select_contents(element: &web_sys::Element) -> bool {
+    let Ok(Some(selection)) = window().get_selection() else {
+        return false;
+    };
+    selection.select_all_children(element).is_ok()
+}
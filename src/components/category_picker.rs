@@ -0,0 +1,33 @@
+use leptos::*;
+use crate::custom::Catalog;
+
+/// Toggle buttons restricting a study mode to some categories. None selected
+/// means the whole catalog.
+#[component]
+pub fn CategoryPicker(catalog: Memo<Catalog>, selected: RwSignal<Vec<&'static str>>) -> impl IntoView {
+    let toggle = move |id: &'static str| {
+        selected.update(|selected| match selected.iter().position(|selected| *selected == id) {
+            Some(index) => {
+                selected.remove(index);
+            }
+            None => selected.push(id),
+        });
+    };
+
+    view! {
+        <div class="category-buttons">
+            {move || catalog.with(|catalog| catalog.categories.iter().map(|category| {
+                let id = category.id;
+                view! {
+                    <button
+                        class="category-button"
+                        class:active=move || selected.with(|selected| selected.contains(&id))
+                        on:click=move |_| toggle(id)
+                    >
+                        {category.name}
+                    </button>
+                }
+            }).collect_view())}
+        </div>
+    }
+}
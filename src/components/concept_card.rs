@@ -1,52 +1,266 @@
 use leptos::*;
+use std::ops::Range;
+use wasm_bindgen::JsCast;
 use crate::ckad_data::Concept;
+use crate::components::CopyButton;
+use crate::custom::CustomConcepts;
+use crate::favorites::Favorites;
+use crate::notes::Notes;
+use crate::placeholders::{fill, merge, slots, Slot};
+use crate::progress::{Progress, Status};
+use crate::routing::Route;
+use crate::search::{segments, MatchSpans};
+use crate::session::PlaceholderValues;
 
-#[component]
-pub fn ConceptCard(concept: Concept) -> impl IntoView {
-    let (is_copied, set_is_copied) = create_signal(false);
-
-    let copy_to_clipboard = move |command: String| {
-        move |_| {
-            if let Some(window) = web_sys::window() {
-                let navigator = window.navigator();
-                let clipboard = navigator.clipboard();
-                let promise = clipboard.write_text(&command);
-                let _ = promise;
-                set_is_copied.set(true);
-
-                set_timeout(
-                    move || {
-                        set_is_copied.set(false);
-                    },
-                    std::time::Duration::from_secs(2)
-                );
+/// Renders `text` with the matched `spans` wrapped in `<mark>`.
+fn highlighted_text(text: &str, spans: &[Range<usize>]) -> View {
+    segments(text, spans)
+        .into_iter()
+        .map(|(segment, is_match)| {
+            let segment = segment.to_string();
+            if is_match {
+                view! { <mark class="search-match">{segment}</mark> }.into_view()
+            } else {
+                segment.into_view()
             }
+        })
+        .collect_view()
+}
+
+/// Renders a command with its slots replaced by the filled-in values (or left
+/// as placeholder names), marking each slot, and the search matches of the
+/// text in between highlighted.
+fn command_text(command: &str, spans: &[Range<usize>], slots: &[Slot], values: PlaceholderValues) -> View {
+    let mut pieces = Vec::with_capacity(slots.len() * 2 + 1);
+    let mut position = 0;
+
+    let plain = |pieces: &mut Vec<View>, range: Range<usize>| {
+        let clipped: Vec<_> = spans
+            .iter()
+            .filter(|span| span.start < range.end && span.end > range.start)
+            .map(|span| span.start.max(range.start) - range.start..span.end.min(range.end) - range.start)
+            .collect();
+        pieces.push(highlighted_text(&command[range], &clipped));
+    };
+
+    for slot in slots {
+        plain(&mut pieces, position..slot.range.start);
+        pieces.push(match values.resolve(slot.name) {
+            Some(value) => view! {
+                <span class="placeholder filled" title=command[slot.range.clone()].to_string()>{value}</span>
+            }.into_view(),
+            None => view! { <span class="placeholder">{slot.name.to_string()}</span> }.into_view(),
+        });
+        position = slot.range.end;
+    }
+    plain(&mut pieces, position..command.len());
+
+    pieces.collect_view()
+}
+
+#[component]
+pub fn ConceptCard(
+    concept: &'static Concept,
+    #[prop(into)] highlighted: Signal<bool>,
+    /// Parts of the text that matched the active search.
+    #[prop(optional)] matches: MatchSpans,
+    /// Shown as a badge when the card is displayed outside its category section.
+    #[prop(optional)] category_name: Option<&'static str>,
+    /// A second copy of the card, shown in the Favorites section. Only the
+    /// copy in the catalog carries the DOM id that deep links scroll to.
+    #[prop(optional)] pinned: bool,
+) -> impl IntoView {
+    let favorites = Favorites::expect();
+    let is_favorite = move || favorites.contains(concept.id);
+
+    let notes = Notes::expect();
+    let (editing_note, set_editing_note) = create_signal(false);
+    let (note_draft, set_note_draft) = create_signal(String::new());
+    let (note_error, set_note_error) = create_signal(None::<String>);
+    let start_editing = move |_| {
+        set_note_draft.set(notes.get(concept.id).unwrap_or_default());
+        set_note_error.set(None);
+        set_editing_note.set(true);
+    };
+    let save_note = move |_| match notes.set(concept.id, &note_draft.get_untracked()) {
+        Ok(()) => set_editing_note.set(false),
+        Err(message) => set_note_error.set(Some(message)),
+    };
+    let note_spans = matches.note.clone();
+
+    let values = PlaceholderValues::expect();
+    // Declared placeholders plus the sample values replaced by the context panel.
+    let card_slots = move || {
+        let mut all = slots(concept.command, concept.placeholders);
+        all.extend(values.stand_in_slots(concept.command));
+        merge(all)
+    };
+    let filled_command = move || fill(concept.command, &card_slots(), |name| values.resolve(name));
+
+    let progress = Progress::expect();
+    let status = move || progress.status(concept.id);
+
+    let custom = CustomConcepts::expect();
+    let is_custom = move || custom.is_custom(concept.id);
+    let delete_concept = move |_| {
+        let confirmed = window()
+            .confirm_with_message(&format!("Delete \"{}\"?", concept.title))
+            .unwrap_or(false);
+        if confirmed {
+            custom.delete(concept.id);
         }
     };
 
-    let command_clone = concept.command.clone();
+    let permalink = Route::Concept(concept.id.to_string()).to_hash();
+
+    // Long YAML blocks scroll inside the card, so bring the first match
+    // into view instead of leaving the reader to hunt for it.
+    let command_ref = create_node_ref::<html::Pre>();
+    if !matches.command.is_empty() {
+        command_ref.on_load(|pre| {
+            request_animation_frame(move || {
+                let first_match = pre
+                    .query_selector("mark")
+                    .ok()
+                    .flatten()
+                    .and_then(|mark| mark.dyn_into::<web_sys::HtmlElement>().ok());
+                if let Some(mark) = first_match {
+                    pre.set_scroll_top((mark.offset_top() - pre.client_height() / 3).max(0));
+                }
+            });
+        });
+    }
 
     view! {
-        <div class="concept-card">
+        <div
+            class="concept-card"
+            class:highlighted=move || highlighted.get()
+            class:mastered=move || status() == Status::Mastered
+            id=(!pinned).then(|| format!("concept-{}", concept.id))
+        >
             <div class="concept-header">
-                <h3 class="concept-title">{concept.title}</h3>
+                <h3 class="concept-title">
+                    {highlighted_text(concept.title, &matches.title)}
+                    <a class="permalink" href=permalink title="Link to this concept">"🔗"</a>
+                </h3>
                 <button
-                    class="copy-button"
-                    class:copied=move || is_copied.get()
-                    on:click=copy_to_clipboard(command_clone)
+                    class="favorite-button"
+                    class:active=is_favorite
+                    title=move || if is_favorite() { "Remove from favorites" } else { "Add to favorites" }
+                    on:click=move |_| favorites.toggle(concept.id)
                 >
-                    {move || if is_copied.get() { "✓ Copied!" } else { "Copy" }}
+                    {move || if is_favorite() { "★" } else { "☆" }}
                 </button>
+                <CopyButton text=Signal::derive(filled_command) select_target=command_ref />
+            </div>
+
+            {category_name.map(|name| {
+                view! {
+                    <span class="concept-category">{name}</span>
+                }
+            })}
+
+            <div class="status-picker" role="group" aria-label="Study status">
+                {Status::ALL.into_iter().map(|option| {
+                    view! {
+                        <button
+                            class=format!("status-button {}", option.class())
+                            class:active=move || status() == option
+                            on:click=move |_| progress.set(concept.id, option)
+                        >
+                            {option.label()}
+                        </button>
+                    }
+                }).collect_view()}
             </div>
 
+            {move || is_custom().then(|| {
+                view! {
+                    <div class="custom-actions">
+                        <span class="custom-badge">"custom"</span>
+                        <button class="note-button secondary" on:click=move |_| custom.start_editing(concept.id)>
+                            "Edit"
+                        </button>
+                        <button class="note-button secondary" on:click=delete_concept>"Delete"</button>
+                    </div>
+                }
+            })}
+
             {concept.description.map(|desc| {
                 view! {
-                    <p class="concept-description">{desc}</p>
+                    <p class="concept-description">
+                        {highlighted_text(desc, &matches.description)}
+                    </p>
+                }
+            })}
+
+            {move || {
+                if editing_note.get() {
+                    return view! {
+                        <div class="concept-note editing">
+                            <textarea
+                                class="note-input"
+                                rows="3"
+                                placeholder="Your own gotchas, e.g. remember --restart=Never here"
+                                prop:value=move || note_draft.get()
+                                on:input=move |ev| set_note_draft.set(event_target_value(&ev))
+                            ></textarea>
+                            {move || note_error.get().map(|message| view! { <p class="note-error">{message}</p> })}
+                            <div class="note-actions">
+                                <button class="note-button" on:click=save_note>"Save"</button>
+                                <button class="note-button secondary" on:click=move |_| set_editing_note.set(false)>
+                                    "Cancel"
+                                </button>
+                            </div>
+                        </div>
+                    }.into_view();
+                }
+
+                match notes.get(concept.id) {
+                    Some(text) => {
+                        // Spans from a search run before the last edit may no longer fit.
+                        let spans: Vec<_> = note_spans
+                            .iter()
+                            .filter(|span| text.get((*span).clone()).is_some())
+                            .cloned()
+                            .collect();
+                        view! {
+                            <div class="concept-note">
+                                <p>{highlighted_text(&text, &spans)}</p>
+                                <button class="note-button secondary" on:click=start_editing>"Edit note"</button>
+                            </div>
+                        }.into_view()
+                    }
+                    None => view! {
+                        <button class="note-add" on:click=start_editing>"📝 Add note"</button>
+                    }.into_view(),
+                }
+            }}
+
+            {(!concept.placeholders.is_empty()).then(|| {
+                view! {
+                    <div class="placeholder-inputs">
+                        {concept.placeholders.iter().map(|&name| {
+                            view! {
+                                <label class="placeholder-input">
+                                    <span>{name}</span>
+                                    <input
+                                        type="text"
+                                        placeholder=move || values.resolve(name).unwrap_or_else(|| name.to_string())
+                                        prop:value=move || values.get(name).unwrap_or_default()
+                                        on:input=move |ev| values.set(name, event_target_value(&ev))
+                                    />
+                                </label>
+                            }
+                        }).collect_view()}
+                    </div>
                 }
             })}
 
-            <pre class="command-block">
-                <code>{concept.command}</code>
+            <pre class="command-block" node_ref=command_ref>
+                <code>
+                    {move || command_text(concept.command, &matches.command, &card_slots(), values)}
+                </code>
             </pre>
         </div>
     }
@@ -0,0 +1,46 @@
+use leptos::*;
+use crate::session::{PlaceholderValues, SESSION_VARIABLES};
+
+/// Namespace, app name, image and labels for the task at hand. Every card
+/// renders and copies its command with these values substituted.
+#[component]
+pub fn ContextPanel() -> impl IntoView {
+    let values = PlaceholderValues::expect();
+
+    let is_active = move || SESSION_VARIABLES.iter().any(|variable| values.get(variable.name).is_some());
+
+    let clear_context = move |_| {
+        for variable in SESSION_VARIABLES {
+            values.set(variable.name, String::new());
+        }
+    };
+
+    view! {
+        <div class="context-panel">
+            <h3>"🎯 Task Context"</h3>
+            <p class="context-hint">"Set these once per exam task; every command below is rendered and copied with them."</p>
+            <div class="context-fields">
+                {SESSION_VARIABLES.iter().map(|variable| {
+                    let name = variable.name;
+
+                    view! {
+                        <label class="context-field">
+                            <span>{variable.label}</span>
+                            <input
+                                type="text"
+                                placeholder=variable.example
+                                prop:value=move || values.get(name).unwrap_or_default()
+                                on:input=move |ev| values.set(name, event_target_value(&ev))
+                            />
+                        </label>
+                    }
+                }).collect_view()}
+            </div>
+            {move || is_active().then(|| {
+                view! {
+                    <button class="reset-button" on:click=clear_context>"Clear Context"</button>
+                }
+            })}
+        </div>
+    }
+}
@@ -0,0 +1,59 @@
+use leptos::*;
+use wasm_bindgen::JsCast;
+use crate::clipboard::{copy_text, CopyOutcome};
+
+/// A Copy button that reports what actually happened: copied, only selected
+/// for a manual copy, or failed.
+#[component]
+pub fn CopyButton(
+    /// The text to copy, read when the button is clicked.
+    #[prop(into)] text: Signal<String>,
+    /// Selected as a last resort when the browser refuses to copy.
+    select_target: NodeRef<html::Pre>,
+) -> impl IntoView {
+    let (outcome, set_outcome) = create_signal(None::<CopyOutcome>);
+    // Only the latest click may reset the button.
+    let clicks = store_value(0u32);
+
+    let on_click = move |_| {
+        let text = text.get_untracked();
+        let target = select_target
+            .get_untracked()
+            .map(|pre| pre.unchecked_ref::<web_sys::Element>().clone());
+        clicks.update_value(|clicks| *clicks += 1);
+        let click = clicks.get_value();
+
+        spawn_local(async move {
+            let result = copy_text(&text, target).await;
+            set_outcome.set(Some(result));
+
+            // Failures stay up longer so they are not missed.
+            let shown_for = if result == CopyOutcome::Copied { 2 } else { 5 };
+            set_timeout(
+                move || {
+                    if clicks.get_value() == click {
+                        set_outcome.set(None);
+                    }
+                },
+                std::time::Duration::from_secs(shown_for),
+            );
+        });
+    };
+
+    view! {
+        <button
+            class="copy-button"
+            class:copied=move || outcome.get() == Some(CopyOutcome::Copied)
+            class:copy-selected=move || outcome.get() == Some(CopyOutcome::Selected)
+            class:copy-failed=move || outcome.get() == Some(CopyOutcome::Failed)
+            on:click=on_click
+        >
+            {move || match outcome.get() {
+                None => "Copy",
+                Some(CopyOutcome::Copied) => "✓ Copied!",
+                Some(CopyOutcome::Selected) => "Selected, press Ctrl+C",
+                Some(CopyOutcome::Failed) => "✗ Copy failed",
+            }}
+        </button>
+    }
+}
@@ -0,0 +1,256 @@
+use leptos::*;
+use crate::ckad_data::get_ckad_concepts;
+use crate::custom::{BundleConcept, CategoryChoice, CustomConcepts};
+use crate::files::{chosen_file, download, read_text};
+use crate::routing::scroll_to_element;
+
+/// Value of the category picker option that creates a new category.
+const NEW_CATEGORY: &str = "";
+
+/// Form to add or edit the reader's own concepts, with export and import of
+/// all of them as a JSON or YAML bundle.
+#[component]
+pub fn CustomConceptForm() -> impl IntoView {
+    let custom = CustomConcepts::expect();
+    let (open, set_open) = create_signal(false);
+    let (status, set_status) = create_signal(None::<Result<String, String>>);
+
+    let title = create_rw_signal(String::new());
+    let command = create_rw_signal(String::new());
+    let description = create_rw_signal(String::new());
+    let placeholders = create_rw_signal(String::new());
+    let equivalents = create_rw_signal(String::new());
+    let category = create_rw_signal(get_ckad_concepts()[0].id.to_string());
+    let new_category = create_rw_signal(String::new());
+    // An imported concept may set its kind and fragment type explicitly;
+    // editing keeps them.
+    let kind = create_rw_signal(None::<String>);
+    let fragment = create_rw_signal(None::<String>);
+
+    let clear = move || {
+        for field in [title, command, description, placeholders, equivalents, new_category] {
+            field.set(String::new());
+        }
+        kind.set(None);
+        fragment.set(None);
+    };
+
+    // Load the concept picked with the Edit button of its card.
+    create_effect(move |_| {
+        if let Some((category_id, concept)) = custom.editing() {
+            title.set(concept.title);
+            command.set(concept.command);
+            description.set(concept.description.unwrap_or_default());
+            placeholders.set(concept.placeholders.join(", "));
+            equivalents.set(concept.equivalents.join("\n"));
+            category.set(category_id);
+            kind.set(concept.kind);
+            fragment.set(concept.fragment);
+            set_status.set(None);
+            set_open.set(true);
+            scroll_to_element("custom-concept-form".to_string());
+        }
+    });
+
+    let save = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        let description = description.get_untracked().trim().to_string();
+        let concept = BundleConcept {
+            // Assigned by `CustomConcepts::save`.
+            id: String::new(),
+            title: title.get_untracked().trim().to_string(),
+            command: command.get_untracked().trim().to_string(),
+            description: (!description.is_empty()).then_some(description),
+            kind: kind.get_untracked(),
+            placeholders: placeholders
+                .get_untracked()
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .collect(),
+            equivalents: equivalents
+                .get_untracked()
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect(),
+            fragment: fragment.get_untracked(),
+        };
+        let choice = match category.get_untracked() {
+            id if id == NEW_CATEGORY => CategoryChoice::New(new_category.get_untracked()),
+            id => CategoryChoice::Existing(id),
+        };
+
+        match custom.save(concept, choice) {
+            Ok(()) => {
+                clear();
+                set_open.set(false);
+                set_status.set(Some(Ok("Concept saved.".to_string())));
+            }
+            Err(message) => set_status.set(Some(Err(message))),
+        }
+    };
+
+    let cancel = move |_| {
+        custom.stop_editing();
+        clear();
+        set_status.set(None);
+        set_open.set(false);
+    };
+
+    let export_json = move |_| {
+        download("ckad-wiki-concepts.json", "application/json", &custom.export_json());
+    };
+    let export_yaml = move |_| {
+        download("ckad-wiki-concepts.yaml", "application/yaml", &custom.export_yaml());
+    };
+
+    let discard_stored = move |_| {
+        let confirmed = window()
+            .confirm_with_message("Discard the saved custom concepts that could not be loaded?")
+            .unwrap_or(false);
+        if confirmed {
+            custom.discard_stored();
+            set_status.set(None);
+        }
+    };
+
+    let import_concepts = move |ev: ev::Event| {
+        let Some(file) = chosen_file(&ev) else {
+            return;
+        };
+        spawn_local(async move {
+            let result = match read_text(file).await {
+                Some(text) => custom
+                    .import(&text)
+                    .map(|count| format!("Imported {count} concept(s).")),
+                None => Err("The file could not be read.".to_string()),
+            };
+            set_status.set(Some(result));
+        });
+    };
+
+    view! {
+        <div class="custom-concepts" id="custom-concept-form">
+            <div class="notes-toolbar">
+                <span class="notes-count">
+                    {move || format!("🧩 {} custom concept(s)", custom.count())}
+                </span>
+                <button class="toolbar-button" on:click=move |_| set_open.update(|open| *open = !*open)>
+                    "Add concept"
+                </button>
+                <button class="toolbar-button" on:click=export_json>"Export JSON"</button>
+                <button class="toolbar-button" on:click=export_yaml>"Export YAML"</button>
+                <label class="toolbar-button">
+                    "Import concepts"
+                    <input
+                        type="file"
+                        accept="application/json,.json,application/yaml,.yaml,.yml"
+                        on:change=import_concepts
+                    />
+                </label>
+                {move || status.get().map(|result| match result {
+                    Ok(message) => view! { <span class="toolbar-status">{message}</span> },
+                    Err(message) => view! { <span class="toolbar-status error">{message}</span> },
+                })}
+            </div>
+
+            {move || custom.load_error().map(|error| view! {
+                <div class="load-error">
+                    <p>"⚠️ Your saved custom concepts could not be loaded: " {error}</p>
+                    <p>
+                        "They are left out of the catalog and kept in storage unchanged. Export them to fix \
+                         them by hand, then discard them here and import the fixed file."
+                    </p>
+                    <button class="note-button secondary" on:click=discard_stored>"Discard saved concepts"</button>
+                </div>
+            })}
+
+            <Show when=move || open.get()>
+                <form class="custom-concept-form" on:submit=save>
+                    <h3>
+                        {move || if custom.editing().is_some() { "✏️ Edit Concept" } else { "🧩 New Concept" }}
+                    </h3>
+                    <label class="context-field">
+                        <span>"Title"</span>
+                        <input
+                            type="text"
+                            placeholder="e.g. Debug with an ephemeral container"
+                            prop:value=move || title.get()
+                            on:input=move |ev| title.set(event_target_value(&ev))
+                        />
+                    </label>
+                    <label class="context-field">
+                        <span>"Command or YAML"</span>
+                        <textarea
+                            class="custom-command-input"
+                            rows="5"
+                            placeholder="k debug -n NAMESPACE POD --image=busybox -it -- sh"
+                            prop:value=move || command.get()
+                            on:input=move |ev| command.set(event_target_value(&ev))
+                        ></textarea>
+                    </label>
+                    <label class="context-field">
+                        <span>"Description (optional)"</span>
+                        <input
+                            type="text"
+                            prop:value=move || description.get()
+                            on:input=move |ev| description.set(event_target_value(&ev))
+                        />
+                    </label>
+                    <label class="context-field">
+                        <span>"Placeholders (optional)"</span>
+                        <input
+                            type="text"
+                            placeholder="e.g. NAMESPACE, POD"
+                            prop:value=move || placeholders.get()
+                            on:input=move |ev| placeholders.set(event_target_value(&ev))
+                        />
+                    </label>
+                    <label class="context-field">
+                        <span>"Also accepted in the typing drill (optional, one per line)"</span>
+                        <textarea
+                            class="custom-command-input"
+                            rows="2"
+                            prop:value=move || equivalents.get()
+                            on:input=move |ev| equivalents.set(event_target_value(&ev))
+                        ></textarea>
+                    </label>
+                    <div class="context-fields">
+                        <label class="context-field">
+                            <span>"Category"</span>
+                            <select
+                                prop:value=move || category.get()
+                                on:change=move |ev| category.set(event_target_value(&ev))
+                            >
+                                {get_ckad_concepts().iter().map(|cat| {
+                                    view! { <option value=cat.id>{cat.name}</option> }
+                                }).collect_view()}
+                                {move || custom.new_categories().into_iter().map(|(id, name)| {
+                                    view! { <option value=id>{name}</option> }
+                                }).collect_view()}
+                                <option value=NEW_CATEGORY>"New category…"</option>
+                            </select>
+                        </label>
+                        <Show when=move || category.get() == NEW_CATEGORY>
+                            <label class="context-field">
+                                <span>"New category name"</span>
+                                <input
+                                    type="text"
+                                    placeholder="e.g. 🧪 My Drills"
+                                    prop:value=move || new_category.get()
+                                    on:input=move |ev| new_category.set(event_target_value(&ev))
+                                />
+                            </label>
+                        </Show>
+                    </div>
+                    <div class="note-actions">
+                        <button type="submit" class="note-button">"Save"</button>
+                        <button type="button" class="note-button secondary" on:click=cancel>"Cancel"</button>
+                    </div>
+                </form>
+            </Show>
+        </div>
+    }
+}
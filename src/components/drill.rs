@@ -0,0 +1,170 @@
+use leptos::*;
+use crate::ckad_data::{Category, Concept, ConceptKind};
+use crate::components::CategoryPicker;
+use crate::custom::Catalog;
+use crate::drill::{check, is_single_line, Change, Verdict};
+
+/// Practice mode that shows a concept's title and asks for its command, typed
+/// out as in the exam terminal. Answers are normalized before grading, and
+/// the verdict shows a token diff against the catalog's command.
+#[component]
+pub fn Drill(catalog: Memo<Catalog>) -> impl IntoView {
+    let selected = create_rw_signal(Vec::<&'static str>::new());
+    let current = create_rw_signal(None::<(&'static Category, &'static Concept)>);
+    let answer = create_rw_signal(String::new());
+    let verdict = create_rw_signal(None::<Verdict>);
+    let (score, set_score) = create_signal((0_usize, 0_usize));
+
+    // Shell commands that fit on one line; YAML is not typed out here.
+    let candidates = create_memo(move |_| {
+        let selected = selected.get();
+        catalog.with(|catalog| {
+            catalog
+                .documents
+                .iter()
+                .filter(|(category, concept)| {
+                    (selected.is_empty() || selected.contains(&category.id))
+                        && concept.kind == ConceptKind::Shell
+                        && is_single_line(concept.command)
+                })
+                .copied()
+                .collect::<Vec<_>>()
+        })
+    });
+
+    let next = move || {
+        let previous = current.get_untracked().map(|(_, concept)| concept.id);
+        let pick = candidates.with_untracked(|candidates| {
+            let others: Vec<_> = candidates
+                .iter()
+                .filter(|(_, concept)| candidates.len() == 1 || Some(concept.id) != previous)
+                .copied()
+                .collect();
+            let index = (js_sys::Math::random() * others.len() as f64) as usize;
+            others.get(index).copied()
+        });
+        current.set(pick);
+        answer.set(String::new());
+        verdict.set(None);
+    };
+
+    let submit = move |ev: ev::SubmitEvent| {
+        ev.prevent_default();
+        let Some((_, concept)) = current.get_untracked() else {
+            return;
+        };
+        if verdict.with_untracked(Option::is_some) || answer.with_untracked(|answer| answer.trim().is_empty()) {
+            return;
+        }
+        let result = answer.with_untracked(|answer| {
+            check(answer, concept.command, concept.equivalents, concept.placeholders)
+        });
+        set_score.update(|(correct, attempts)| {
+            *attempts += 1;
+            if result.correct {
+                *correct += 1;
+            }
+        });
+        verdict.set(Some(result));
+    };
+
+    let result = move |concept: &'static Concept, outcome: Verdict| {
+        view! {
+            <div class="drill-result" class:correct=outcome.correct>
+                <p class="drill-verdict">
+                    {if outcome.correct { "✅ Correct" } else { "❌ Not quite" }}
+                </p>
+                <p class="drill-diff">
+                    {outcome.diff.into_iter().map(|token| {
+                        let class = match token.change {
+                            Change::Same => "diff-token",
+                            Change::Missing => "diff-token missing",
+                            Change::Extra => "diff-token extra",
+                        };
+                        view! { <span class=class>{token.text}</span> }
+                    }).collect_view()}
+                </p>
+                <pre class="command-block"><code>{concept.command}</code></pre>
+                {(!concept.equivalents.is_empty()).then(|| view! {
+                    <p class="context-hint">"Also accepted: " {concept.equivalents.join(" · ")}</p>
+                })}
+            </div>
+        }
+    };
+
+    view! {
+        <div class="study-mode">
+            <h2 class="category-title">"⌨️ Type the Command"</h2>
+            <div class="study-setup">
+                <CategoryPicker catalog=catalog selected=selected />
+                <div class="study-status">
+                    <span>{move || format!("{} command(s) to practice", candidates.with(Vec::len))}</span>
+                    <span>{move || {
+                        let (correct, attempts) = score.get();
+                        format!("Score: {correct}/{attempts}")
+                    }}</span>
+                </div>
+            </div>
+            {move || match current.get() {
+                None => view! {
+                    <div class="study-done">
+                        <button
+                            class="study-button"
+                            disabled=move || candidates.with(Vec::is_empty)
+                            on:click=move |_| next()
+                        >
+                            "Start"
+                        </button>
+                    </div>
+                }.into_view(),
+                Some((category, concept)) => view! {
+                    <form class="flashcard" on:submit=submit>
+                        <span class="concept-category">{category.name}</span>
+                        <h3 class="concept-title">{concept.title}</h3>
+                        {concept.description.map(|description| {
+                            view! { <p class="concept-description">{description}</p> }
+                        })}
+                        {(!concept.placeholders.is_empty()).then(|| view! {
+                            <p class="context-hint">
+                                "Any value works for " {concept.placeholders.join(", ")}
+                            </p>
+                        })}
+                        <input
+                            type="text"
+                            class="drill-input"
+                            placeholder="k ..."
+                            autocomplete="off"
+                            spellcheck="false"
+                            prop:value=move || answer.get()
+                            prop:disabled=move || verdict.with(Option::is_some)
+                            on:input=move |ev| answer.set(event_target_value(&ev))
+                        />
+                        {move || match verdict.get() {
+                            None => view! {
+                                <div class="note-actions">
+                                    <button type="submit" class="note-button">"Check"</button>
+                                    <button type="button" class="note-button secondary" on:click=move |_| next()>
+                                        "Skip"
+                                    </button>
+                                </div>
+                            }.into_view(),
+                            Some(outcome) => view! {
+                                {result(concept, outcome)}
+                                <div class="note-actions">
+                                    <button type="button" class="note-button" on:click=move |_| next()>"Next"</button>
+                                    <button
+                                        type="button"
+                                        class="note-button secondary"
+                                        on:click=move |_| verdict.set(None)
+                                    >
+                                        "Try again"
+                                    </button>
+                                </div>
+                            }.into_view(),
+                        }}
+                    </form>
+                }.into_view(),
+            }}
+        </div>
+    }
+}
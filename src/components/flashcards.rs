@@ -0,0 +1,169 @@
+use leptos::*;
+use std::collections::{BTreeMap, HashMap};
+use crate::ckad_data::{Category, Concept};
+use crate::components::CategoryPicker;
+use crate::custom::Catalog;
+use crate::srs::{due_queue, Day, Grade, ReviewState};
+use crate::storage;
+
+const STORAGE_KEY: &str = "reviews";
+
+/// Never-reviewed cards introduced per session, so a fresh catalog is not
+/// served all at once.
+const NEW_CARDS_PER_SESSION: usize = 20;
+
+/// Today's date in the reader's time zone, so cards fall due at local
+/// midnight.
+fn today() -> Day {
+    let now = js_sys::Date::new_0();
+    let local_ms = now.get_time() - now.get_timezone_offset() * 60_000.0;
+    (local_ms / 86_400_000.0).floor() as Day
+}
+
+/// Study mode that turns every concept into a flashcard: recall the command
+/// from the title and description, reveal it and grade the recall. Reviews
+/// are scheduled with SM-2 and persisted in `localStorage`.
+#[component]
+pub fn Flashcards(catalog: Memo<Catalog>) -> impl IntoView {
+    let reviews = create_rw_signal(
+        storage::load::<BTreeMap<String, ReviewState>>(STORAGE_KEY).unwrap_or_default(),
+    );
+    // Categories to study; none selected means all of them.
+    let selected = create_rw_signal(Vec::<&'static str>::new());
+    let queue = create_rw_signal(Vec::<(&'static Category, &'static Concept)>::new());
+    let (in_session, set_in_session) = create_signal(false);
+    let (revealed, set_revealed) = create_signal(false);
+    let (reviewed_count, set_reviewed_count) = create_signal(0_usize);
+
+    let due_cards = create_memo(move |_| {
+        let selected = selected.get();
+        catalog.with(|catalog| reviews.with(|reviews| {
+            let cards: Vec<_> = catalog
+                .documents
+                .iter()
+                .filter(|(category, _)| selected.is_empty() || selected.contains(&category.id))
+                .copied()
+                .collect();
+            let by_id: HashMap<_, _> = cards.iter().map(|&(category, concept)| (concept.id, (category, concept))).collect();
+
+            due_queue(cards.iter().map(|(_, concept)| concept.id), reviews, today(), NEW_CARDS_PER_SESSION)
+                .into_iter()
+                .map(|id| by_id[id])
+                .collect::<Vec<_>>()
+        }))
+    });
+
+    let start = move |_| {
+        queue.set(due_cards.get_untracked());
+        set_reviewed_count.set(0);
+        set_revealed.set(false);
+        set_in_session.set(true);
+    };
+
+    let grade = move |grade: Grade| {
+        let Some((category, concept)) = queue.with_untracked(|queue| queue.first().copied()) else {
+            return;
+        };
+        reviews.update(|reviews| {
+            let state = reviews.get(concept.id).copied().unwrap_or_default();
+            reviews.insert(concept.id.to_string(), state.review(grade, today()));
+            storage::save(STORAGE_KEY, reviews);
+        });
+        queue.update(|queue| {
+            queue.remove(0);
+            // A forgotten card comes back at the end of the session.
+            if grade == Grade::Again {
+                queue.push((category, concept));
+            }
+        });
+        set_reviewed_count.update(|count| *count += 1);
+        set_revealed.set(false);
+    };
+
+    // Days until the card would be due again after each grade.
+    let interval_after = move |id: &str, grade: Grade| {
+        let state = reviews.with(|reviews| reviews.get(id).copied().unwrap_or_default());
+        state.review(grade, today()).interval_days
+    };
+
+    let setup = move || {
+        view! {
+            <div class="study-setup">
+                <p class="context-hint">"Pick categories to study, or none for the whole catalog."</p>
+                <CategoryPicker catalog=catalog selected=selected />
+                <button
+                    class="study-button"
+                    disabled=move || due_cards.with(Vec::is_empty)
+                    on:click=start
+                >
+                    {move || match due_cards.with(Vec::len) {
+                        0 => "Nothing due — come back tomorrow".to_string(),
+                        count => format!("Study {count} card(s)"),
+                    }}
+                </button>
+            </div>
+        }
+    };
+
+    let card = move |(category, concept): (&'static Category, &'static Concept)| {
+        view! {
+            <div class="flashcard">
+                <span class="concept-category">{category.name}</span>
+                <h3 class="concept-title">{concept.title}</h3>
+                {concept.description.map(|description| {
+                    view! { <p class="concept-description">{description}</p> }
+                })}
+                {move || if revealed.get() {
+                    view! {
+                        <pre class="command-block"><code>{concept.command}</code></pre>
+                        <div class="grade-buttons">
+                            {Grade::ALL.into_iter().map(|option| {
+                                view! {
+                                    <button
+                                        class=format!("grade-button grade-{}", option.label().to_lowercase())
+                                        on:click=move |_| grade(option)
+                                    >
+                                        {option.label()}
+                                        <small>{move || format!("{}d", interval_after(concept.id, option))}</small>
+                                    </button>
+                                }
+                            }).collect_view()}
+                        </div>
+                    }.into_view()
+                } else {
+                    view! {
+                        <button class="study-button" on:click=move |_| set_revealed.set(true)>
+                            "Show command"
+                        </button>
+                    }.into_view()
+                }}
+            </div>
+        }
+    };
+
+    view! {
+        <div class="study-mode">
+            <h2 class="category-title">"🃏 Flashcards"</h2>
+            {move || {
+                if !in_session.get() {
+                    return setup().into_view();
+                }
+                match queue.with(|queue| queue.first().copied()) {
+                    Some(next) => view! {
+                        <div class="study-status">
+                            <span>{move || format!("{} left · {} reviewed", queue.with(Vec::len), reviewed_count.get())}</span>
+                            <button class="reset-button" on:click=move |_| set_in_session.set(false)>"End session"</button>
+                        </div>
+                        {card(next)}
+                    }.into_view(),
+                    None => view! {
+                        <div class="study-done">
+                            <p>{move || format!("🎉 Session complete: {} review(s).", reviewed_count.get())}</p>
+                            <button class="study-button" on:click=move |_| set_in_session.set(false)>"Back"</button>
+                        </div>
+                    }.into_view(),
+                }
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,143 @@
+use leptos::*;
+use crate::catalog_rules::FRAGMENT_TYPES;
+use crate::manifest::{validate_manifests, validate_snippet, Problem};
+
+/// Shown in the empty editor: what to paste, with a typo to catch.
+const EXAMPLE: &str = "apiVersion: apps/v1
+kind: Deployment
+metadata:
+  name: web
+spec:
+  replicas: 2
+  selector:
+    matchLabels:
+      app: web
+  template:
+    metadata:
+      labels:
+        app: web
+    spec:
+      containers:
+      - name: web
+        image: nginx
+        readinessProbe:
+          httpGet:
+            port: 80
+          initialDelaySecond: 5
+---
+apiVersion: v1
+kind: Service
+...";
+
+/// Selects a 1-based line of the editor, so that a problem can be found in
+/// the pasted text. Offsets are in UTF-16 units, as the DOM counts them.
+fn select_line(editor: &web_sys::HtmlTextAreaElement, line: usize) {
+    let text = editor.value();
+    let mut lines = text.split('\n');
+    let start: usize = lines.by_ref().take(line - 1).map(|line| line.encode_utf16().count() + 1).sum();
+    let length = lines.next().map_or(0, |line| line.encode_utf16().count());
+    let _ = editor.focus();
+    let _ = editor.set_selection_range(start as u32, (start + length) as u32);
+}
+
+/// Checks pasted YAML against the trimmed Kubernetes schema the catalog
+/// tests use, so a manifest can be trusted before it is typed out in the
+/// exam terminal.
+#[component]
+pub fn ManifestValidator() -> impl IntoView {
+    let text = create_rw_signal(String::new());
+    // Empty for complete manifests, otherwise the fragment type to check.
+    let fragment = create_rw_signal(String::new());
+    let editor = create_node_ref::<html::Textarea>();
+
+    let problems = create_memo(move |_| {
+        text.with(|text| {
+            if text.trim().is_empty() {
+                return Vec::new();
+            }
+            fragment.with(|fragment| match fragment.as_str() {
+                "" => validate_manifests(text),
+                fragment => validate_snippet(text, Some(fragment)),
+            })
+        })
+    });
+
+    let problem_view = move |problem: Problem| {
+        let line = problem.line;
+        let show_line = move |_| {
+            if let Some(editor) = editor.get_untracked() {
+                select_line(&editor, line);
+            }
+        };
+        view! {
+            <li class="validator-problem">
+                <button class="validator-line" title="Select this line" on:click=show_line>
+                    {format!("Line {line}")}
+                </button>
+                <div>
+                    <p>{problem.message}</p>
+                    {problem.hint.map(|hint| view! { <p class="validator-hint">"💡 " {hint}</p> })}
+                </div>
+            </li>
+        }
+    };
+
+    view! {
+        <div class="study-mode">
+            <h2 class="category-title">"✅ Validate Manifests"</h2>
+            <p class="context-hint">
+                "Paste one or more YAML documents, separated by ---. They are checked against the same \
+                 trimmed Kubernetes schema as the catalog: unknown fields, wrong types and missing required \
+                 fields of the kinds the CKAD covers."
+            </p>
+            <div class="validator-options">
+                <label class="context-field">
+                    <span>"Check as"</span>
+                    <select
+                        prop:value=move || fragment.get()
+                        on:change=move |ev| fragment.set(event_target_value(&ev))
+                    >
+                        <option value="">"Complete manifests"</option>
+                        {FRAGMENT_TYPES.iter().map(|name| view! {
+                            <option value=*name>{format!("Fragment: {name}")}</option>
+                        }).collect_view()}
+                    </select>
+                </label>
+                <button
+                    class="note-button secondary"
+                    disabled=move || text.with(String::is_empty)
+                    on:click=move |_| text.set(String::new())
+                >
+                    "Clear"
+                </button>
+            </div>
+            <textarea
+                class="validator-input"
+                rows="18"
+                spellcheck="false"
+                autocomplete="off"
+                placeholder=EXAMPLE
+                node_ref=editor
+                prop:value=move || text.get()
+                on:input=move |ev| text.set(event_target_value(&ev))
+            ></textarea>
+            {move || {
+                let count = problems.with(Vec::len);
+                let status = match count {
+                    _ if text.with(|text| text.trim().is_empty()) => return None,
+                    0 => "✅ No problems found".to_string(),
+                    1 => "❌ 1 problem".to_string(),
+                    count => format!("❌ {count} problems"),
+                };
+                Some(view! {
+                    <div class="validator-result" class:valid=count == 0>
+                        <p class="validator-status">{status}</p>
+                        <ul class="validator-problems">
+                            {problems.get().into_iter().map(problem_view).collect_view()}
+                        </ul>
+                    </div>
+                })
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,468 @@
+use leptos::*;
+use leptos::leptos_dom::helpers::IntervalHandle;
+use std::time::Duration;
+use crate::ckad_data::{get_exam_tasks, Concept, Domain, ExamTask};
+use crate::custom::Catalog;
+use crate::exam::{load_history, pick_tasks, save_history, ExamResult, PASS_PERCENT};
+use crate::rng::Rng;
+use crate::routing::Route;
+
+/// The real exam has 15 to 20 tasks in two hours.
+const TASK_COUNTS: [usize; 4] = [5, 10, 16, 20];
+const DEFAULT_TASK_COUNT: usize = 16;
+const DEFAULT_MINUTES: u32 = 120;
+
+/// Remaining time below which the timer turns red.
+const LOW_TIME_MS: f64 = 5.0 * 60_000.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Phase {
+    Setup,
+    Running,
+    Review,
+    Report,
+}
+
+/// What the candidate noted about a task during the exam, and the
+/// self-assessed outcome afterwards.
+#[derive(Clone, Copy, Debug, Default)]
+struct Mark {
+    flagged: bool,
+    done: bool,
+    solved: bool,
+}
+
+fn format_duration(seconds: u32) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else {
+        format!("{minutes:02}:{seconds:02}")
+    }
+}
+
+fn format_date(timestamp: f64) -> String {
+    String::from(js_sys::Date::new(&timestamp.into()).to_locale_date_string("default", &Default::default()))
+}
+
+/// A timed exam of tasks drawn in proportion to the CKAD curriculum domains.
+/// Tasks can be flagged and marked done while the clock runs; afterwards the
+/// candidate checks each one against its linked concepts and gets a report
+/// with per-domain scores. Results are kept in `localStorage` and charted.
+#[component]
+pub fn MockExam(catalog: Memo<Catalog>) -> impl IntoView {
+    let phase = create_rw_signal(Phase::Setup);
+    let task_count = create_rw_signal(DEFAULT_TASK_COUNT);
+    let minutes = create_rw_signal(DEFAULT_MINUTES);
+    let tasks = create_rw_signal(Vec::<&'static ExamTask>::new());
+    let marks = create_rw_signal(Vec::<Mark>::new());
+    let current = create_rw_signal(0_usize);
+    let history = create_rw_signal(load_history());
+    let report = create_rw_signal(None::<ExamResult>);
+
+    // Wall-clock times in milliseconds; `now` ticks once a second.
+    let started_at = store_value(0.0_f64);
+    let deadline = create_rw_signal(0.0_f64);
+    let now = create_rw_signal(0.0_f64);
+    let seconds_used = store_value(0_u32);
+    let timed_out = store_value(false);
+    let timer = store_value(None::<IntervalHandle>);
+
+    let stop_timer = move || {
+        timer.update_value(|timer| {
+            if let Some(handle) = timer.take() {
+                handle.clear();
+            }
+        });
+    };
+    on_cleanup(stop_timer);
+
+    let finish = move || {
+        stop_timer();
+        let end = js_sys::Date::now().min(deadline.get_untracked());
+        seconds_used.set_value(((end - started_at.get_value()) / 1000.0).max(0.0) as u32);
+        // Tasks marked done are assumed solved until checked.
+        marks.update(|marks| marks.iter_mut().for_each(|mark| mark.solved = mark.done));
+        phase.set(Phase::Review);
+    };
+
+    let start = move |_| {
+        let mut rng = Rng::new(Rng::random_seed());
+        let picked = pick_tasks(get_exam_tasks(), task_count.get_untracked(), &mut rng);
+        marks.set(vec![Mark::default(); picked.len()]);
+        tasks.set(picked);
+        current.set(0);
+        timed_out.set_value(false);
+
+        let start_ms = js_sys::Date::now();
+        started_at.set_value(start_ms);
+        now.set(start_ms);
+        deadline.set(start_ms + f64::from(minutes.get_untracked()) * 60_000.0);
+        stop_timer();
+        let handle = set_interval_with_handle(
+            move || {
+                let time = js_sys::Date::now();
+                now.set(time);
+                if time >= deadline.get_untracked() && phase.get_untracked() == Phase::Running {
+                    timed_out.set_value(true);
+                    finish();
+                }
+            },
+            Duration::from_secs(1),
+        );
+        timer.set_value(handle.ok());
+        phase.set(Phase::Running);
+    };
+
+    let submit = move |_| {
+        let open = marks.with_untracked(|marks| marks.iter().filter(|mark| !mark.done).count());
+        let message = match open {
+            0 => "Submit the exam?".to_string(),
+            open => format!("{open} task(s) are not marked done. Submit the exam anyway?"),
+        };
+        if window().confirm_with_message(&message).unwrap_or(false) {
+            finish();
+        }
+    };
+
+    let record = move |_| {
+        let result = tasks.with_untracked(|tasks| marks.with_untracked(|marks| {
+            let solved: Vec<bool> = marks.iter().map(|mark| mark.solved).collect();
+            ExamResult::new(
+                tasks,
+                &solved,
+                minutes.get_untracked(),
+                seconds_used.get_value(),
+                js_sys::Date::now(),
+            )
+        }));
+        history.update(|history| {
+            history.push(result.clone());
+            save_history(history);
+        });
+        report.set(Some(result));
+        phase.set(Phase::Report);
+    };
+
+    let update_mark = move |index: usize, change: fn(&mut Mark)| {
+        marks.update(|marks| {
+            if let Some(mark) = marks.get_mut(index) {
+                change(mark);
+            }
+        });
+    };
+
+    let concept = move |id: &str| -> Option<&'static Concept> {
+        catalog.with(|catalog| {
+            catalog.documents.iter().find(|(_, concept)| concept.id == id).map(|&(_, concept)| concept)
+        })
+    };
+
+    let remaining_ms = move || (deadline.get() - now.get()).max(0.0);
+
+    let history_chart = move || {
+        history.with(|history| {
+            if history.is_empty() {
+                return view! {
+                    <p class="context-hint">"Your results will be charted here."</p>
+                }.into_view();
+            }
+            const WIDTH: f64 = 600.0;
+            const HEIGHT: f64 = 160.0;
+            let x = |index: usize| match history.len() {
+                1 => WIDTH / 2.0,
+                len => index as f64 * WIDTH / (len - 1) as f64,
+            };
+            let y = |percent: f64| HEIGHT - percent / 100.0 * HEIGHT;
+            let points: Vec<String> = history
+                .iter()
+                .enumerate()
+                .map(|(index, result)| format!("{:.1},{:.1}", x(index), y(result.total.percent())))
+                .collect();
+            let last = history.last().unwrap();
+            let best = history.iter().map(|result| result.total.percent()).fold(0.0, f64::max);
+
+            view! {
+                <div class="exam-history">
+                    <div class="progress-summary">
+                        <h3>"📈 Results"</h3>
+                        <span class="context-hint">
+                            {format!(
+                                "{} exam(s) · last {:.0}% · best {:.0}%",
+                                history.len(),
+                                last.total.percent(),
+                                best,
+                            )}
+                        </span>
+                    </div>
+                    <svg class="exam-chart" viewBox=format!("-10 -10 {} {}", WIDTH + 20.0, HEIGHT + 20.0)>
+                        <line class="exam-pass-line" x1="0" x2=WIDTH y1=y(PASS_PERCENT) y2=y(PASS_PERCENT)></line>
+                        <polyline class="exam-chart-line" points=points.join(" ")></polyline>
+                        {history.iter().enumerate().map(|(index, result)| {
+                            let title = format!(
+                                "{}: {:.0}% ({}/{} points)",
+                                format_date(result.finished_at),
+                                result.total.percent(),
+                                result.total.earned,
+                                result.total.possible,
+                            );
+                            view! {
+                                <circle
+                                    class="exam-chart-point"
+                                    class:passed=result.passed()
+                                    cx=x(index)
+                                    cy=y(result.total.percent())
+                                    r="5"
+                                >
+                                    <title>{title}</title>
+                                </circle>
+                            }
+                        }).collect_view()}
+                    </svg>
+                </div>
+            }.into_view()
+        })
+    };
+
+    let setup = move || {
+        view! {
+            <div class="study-setup">
+                <p class="context-hint">
+                    {format!(
+                        "Tasks are drawn in proportion to the curriculum domains. Score {PASS_PERCENT:.0}% to pass."
+                    )}
+                </p>
+                <ul class="exam-weights">
+                    {Domain::ALL.into_iter().map(|domain| view! {
+                        <li><span>{domain.name()}</span><strong>{format!("{}%", domain.weight())}</strong></li>
+                    }).collect_view()}
+                </ul>
+                <div class="exam-options">
+                    <label>
+                        "Tasks "
+                        <select on:change=move |ev| {
+                            if let Ok(count) = event_target_value(&ev).parse() {
+                                task_count.set(count);
+                            }
+                        }>
+                            {TASK_COUNTS.into_iter().map(|count| view! {
+                                <option value=count.to_string() selected=move || task_count.get() == count>
+                                    {count}
+                                </option>
+                            }).collect_view()}
+                        </select>
+                    </label>
+                    <label>
+                        "Minutes "
+                        <input
+                            type="number"
+                            min="5"
+                            max="240"
+                            prop:value=move || minutes.get().to_string()
+                            on:change=move |ev| {
+                                if let Ok(value) = event_target_value(&ev).parse::<u32>() {
+                                    minutes.set(value.clamp(5, 240));
+                                }
+                            }
+                        />
+                    </label>
+                </div>
+                <button class="study-button" on:click=start>"Start exam"</button>
+            </div>
+            {history_chart}
+        }
+    };
+
+    let task_card = move |index: usize| {
+        let task = tasks.with(|tasks| tasks[index]);
+        let mark = move || marks.with(|marks| marks[index]);
+        let last = tasks.with(Vec::len) - 1;
+        view! {
+            <div class="flashcard exam-task">
+                <span class="concept-category">
+                    {format!("{} · {} points", task.domain.name(), task.points)}
+                </span>
+                <h3 class="concept-title">{format!("Task {} — {}", index + 1, task.title)}</h3>
+                <p class="exam-task-text">{task.task}</p>
+                <div class="note-actions">
+                    <button class="note-button secondary" on:click=move |_| update_mark(index, |mark| mark.flagged = !mark.flagged)>
+                        {move || if mark().flagged { "🚩 Unflag" } else { "🏳️ Flag for later" }}
+                    </button>
+                    <button class="note-button" on:click=move |_| update_mark(index, |mark| mark.done = !mark.done)>
+                        {move || if mark().done { "↩️ Not done" } else { "✅ Mark done" }}
+                    </button>
+                </div>
+                <div class="exam-paging">
+                    <button class="note-button secondary" disabled=index == 0 on:click=move |_| current.set(index - 1)>
+                        "← Previous"
+                    </button>
+                    <button class="note-button secondary" disabled=index == last on:click=move |_| current.set(index + 1)>
+                        "Next →"
+                    </button>
+                </div>
+            </div>
+        }
+    };
+
+    let running = move || {
+        view! {
+            <div class="study-status">
+                <span class="exam-timer" class:low=move || remaining_ms() < LOW_TIME_MS>
+                    {move || format!("⏱️ {}", format_duration((remaining_ms() / 1000.0).ceil() as u32))}
+                </span>
+                <span>{move || marks.with(|marks| format!(
+                    "{}/{} done · {} flagged",
+                    marks.iter().filter(|mark| mark.done).count(),
+                    marks.len(),
+                    marks.iter().filter(|mark| mark.flagged).count(),
+                ))}</span>
+                <button class="reset-button" on:click=submit>"Submit exam"</button>
+            </div>
+            <div class="exam-navigator">
+                {move || (0..tasks.with(Vec::len)).map(|index| {
+                    let mark = move || marks.with(|marks| marks[index]);
+                    view! {
+                        <button
+                            class="exam-task-link"
+                            class:current=move || current.get() == index
+                            class:done=move || mark().done
+                            class:flagged=move || mark().flagged
+                            on:click=move |_| current.set(index)
+                        >
+                            {index + 1}
+                        </button>
+                    }
+                }).collect_view()}
+            </div>
+            {move || task_card(current.get())}
+        }
+    };
+
+    let review = move || {
+        view! {
+            <p class="context-hint exam-review-intro">
+                {if timed_out.get_value() { "⏰ Time is up. " } else { "" }}
+                {format!("Finished in {}. ", format_duration(seconds_used.get_value()))}
+                "Compare your work with the linked cards and tick every task you solved."
+            </p>
+            {tasks.get_untracked().into_iter().enumerate().map(|(index, task)| {
+                let mark = move || marks.with(|marks| marks[index]);
+                view! {
+                    <div class="exam-review-task" class:solved=move || mark().solved>
+                        <span class="concept-category">
+                            {format!("{} · {} points", task.domain.name(), task.points)}
+                            {move || mark().flagged.then_some(" · 🚩 flagged")}
+                        </span>
+                        <h3 class="concept-title">{format!("Task {} — {}", index + 1, task.title)}</h3>
+                        <p class="exam-task-text">{task.task}</p>
+                        <details>
+                            <summary>"Solution cards"</summary>
+                            {task.concepts.iter().filter_map(|&id| concept(id)).map(|concept| view! {
+                                <h4 class="exam-solution-title">{concept.title}</h4>
+                                <pre class="command-block"><code>{concept.command}</code></pre>
+                            }).collect_view()}
+                        </details>
+                        <label class="hide-mastered">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || mark().solved
+                                on:change=move |ev| {
+                                    let solved = event_target_checked(&ev);
+                                    marks.update(|marks| marks[index].solved = solved);
+                                }
+                            />
+                            "Solved"
+                        </label>
+                    </div>
+                }
+            }).collect_view()}
+            <div class="study-done">
+                <button class="study-button" on:click=record>"Show report"</button>
+            </div>
+        }
+    };
+
+    let report_view = move || {
+        let Some(result) = report.get() else {
+            return ().into_view();
+        };
+        let missed: Vec<&'static ExamTask> = result
+            .missed
+            .iter()
+            .filter_map(|id| get_exam_tasks().iter().find(|task| task.id == id))
+            .collect();
+
+        view! {
+            <div class="exam-report">
+                <p class="exam-total" class:passed=result.passed()>
+                    {format!("{:.0}%", result.total.percent())}
+                    <small>
+                        {if result.passed() {
+                            "Passed".to_string()
+                        } else {
+                            format!("Not passed — {PASS_PERCENT:.0}% needed")
+                        }}
+                    </small>
+                </p>
+                <p class="context-hint">
+                    {format!(
+                        "{}/{} points · {} of {} minutes used",
+                        result.total.earned,
+                        result.total.possible,
+                        format_duration(result.seconds_used),
+                        result.time_limit_minutes,
+                    )}
+                </p>
+                <div class="exam-domains">
+                    {result.domains.iter().filter_map(|(slug, &score)| {
+                        let domain = Domain::from_slug(slug)?;
+                        Some(view! {
+                            <div class="exam-domain">
+                                <div class="exam-domain-label">
+                                    <span>{domain.name()}</span>
+                                    <span>{format!("{}/{} · {:.0}%", score.earned, score.possible, score.percent())}</span>
+                                </div>
+                                <div class="progress-bar">
+                                    <div
+                                        class="progress-segment status-mastered"
+                                        style:width=format!("{:.1}%", score.percent())
+                                    ></div>
+                                </div>
+                            </div>
+                        })
+                    }).collect_view()}
+                </div>
+                {(!missed.is_empty()).then(|| view! {
+                    <h3 class="exam-missed-title">"Missed tasks"</h3>
+                    <ul class="exam-missed">
+                        {missed.into_iter().map(|task| view! {
+                            <li>
+                                <strong>{task.title}</strong>
+                                <span class="exam-missed-links">
+                                    {task.concepts.iter().filter_map(|&id| concept(id)).map(|concept| view! {
+                                        <a href=Route::Concept(concept.id.to_string()).to_hash()>{concept.title}</a>
+                                    }).collect_view()}
+                                </span>
+                            </li>
+                        }).collect_view()}
+                    </ul>
+                })}
+                <div class="study-done">
+                    <button class="study-button" on:click=move |_| phase.set(Phase::Setup)>"New exam"</button>
+                </div>
+            </div>
+        }.into_view()
+    };
+
+    view! {
+        <div class="study-mode">
+            <h2 class="category-title">"⏱️ Mock Exam"</h2>
+            {move || match phase.get() {
+                Phase::Setup => setup().into_view(),
+                Phase::Running => running().into_view(),
+                Phase::Review => review().into_view(),
+                Phase::Report => report_view(),
+            }}
+        </div>
+    }
+}
@@ -1,7 +1,35 @@
 mod wiki_component;
 mod concept_card;
 mod search_bar;
+mod context_panel;
+mod copy_button;
+mod notes_toolbar;
+mod custom_concept_form;
+mod progress_bar;
+mod progress_dashboard;
+mod mode_nav;
+mod flashcards;
+mod category_picker;
+mod drill;
+mod mock_exam;
+mod quiz;
+mod manifest_validator;
+mod pod_builder;
 
 pub use wiki_component::CkadWiki;
 pub use concept_card::ConceptCard;
 pub use search_bar::SearchBar;
+pub use context_panel::ContextPanel;
+pub use copy_button::CopyButton;
+pub use notes_toolbar::NotesToolbar;
+pub use custom_concept_form::CustomConceptForm;
+pub use progress_bar::ProgressBar;
+pub use progress_dashboard::ProgressDashboard;
+pub use mode_nav::ModeNav;
+pub use flashcards::Flashcards;
+pub use category_picker::CategoryPicker;
+pub use drill::Drill;
+pub use mock_exam::MockExam;
+pub use quiz::Quiz;
+pub use manifest_validator::ManifestValidator;
+pub use pod_builder::PodBuilder;
@@ -0,0 +1,34 @@
+use leptos::*;
+use crate::routing::Route;
+
+/// Links between the catalog and the study modes.
+#[component]
+pub fn ModeNav(route: ReadSignal<Route>) -> impl IntoView {
+    let modes = [
+        ("📚 Wiki", Route::Home),
+        ("🃏 Flashcards", Route::Flashcards),
+        ("⌨️ Drill", Route::Drill),
+        ("❓ Quiz", Route::Quiz(None)),
+        ("⏱️ Mock Exam", Route::Exam),
+        ("✅ Validate", Route::Validate),
+        ("🧱 Pod Builder", Route::PodBuilder),
+    ];
+
+    view! {
+        <nav class="mode-nav">
+            {modes.into_iter().map(|(label, target)| {
+                let href = target.to_hash();
+                let is_active = move || route.with(|route| {
+                    if target.is_wiki() {
+                        route.is_wiki()
+                    } else {
+                        std::mem::discriminant(route) == std::mem::discriminant(&target)
+                    }
+                });
+                view! {
+                    <a class="mode-link" class:active=is_active href=href>{label}</a>
+                }
+            }).collect_view()}
+        </nav>
+    }
+}
@@ -0,0 +1,67 @@
+use leptos::*;
+use crate::files::{chosen_file, download, read_text};
+use crate::notes::Notes;
+
+/// Export and import of all personal notes as a JSON file, to move them
+/// between machines.
+#[component]
+pub fn NotesToolbar() -> impl IntoView {
+    let notes = Notes::expect();
+    let (status, set_status) = create_signal(None::<Result<String, String>>);
+
+    let export_notes = move |_| {
+        download("ckad-wiki-notes.json", "application/json", &notes.export());
+    };
+
+    let discard_stored = move |_| {
+        let confirmed = window()
+            .confirm_with_message("Discard the saved notes that could not be loaded?")
+            .unwrap_or(false);
+        if confirmed {
+            notes.discard_stored();
+            set_status.set(None);
+        }
+    };
+
+    let import_notes = move |ev: ev::Event| {
+        let Some(file) = chosen_file(&ev) else {
+            return;
+        };
+        spawn_local(async move {
+            let result = match read_text(file).await {
+                Some(text) => notes
+                    .import(&text)
+                    .map(|count| format!("Imported {count} note(s).")),
+                None => Err("The file could not be read.".to_string()),
+            };
+            set_status.set(Some(result));
+        });
+    };
+
+    view! {
+        {move || notes.load_error().map(|error| view! {
+            <div class="load-error">
+                <p>"⚠️ Your saved notes could not be loaded: " {error}</p>
+                <p>
+                    "They are kept in storage unchanged. Export them to fix them by hand, then discard them \
+                     here and import the fixed file."
+                </p>
+                <button class="note-button secondary" on:click=discard_stored>"Discard saved notes"</button>
+            </div>
+        })}
+        <div class="notes-toolbar">
+            <span class="notes-count">
+                {move || format!("📝 {} personal note(s)", notes.with(|notes| notes.len()))}
+            </span>
+            <button class="toolbar-button" on:click=export_notes>"Export notes"</button>
+            <label class="toolbar-button">
+                "Import notes"
+                <input type="file" accept="application/json,.json" on:change=import_notes />
+            </label>
+            {move || status.get().map(|result| match result {
+                Ok(message) => view! { <span class="toolbar-status">{message}</span> },
+                Err(message) => view! { <span class="toolbar-status error">{message}</span> },
+            })}
+        </div>
+    }
+}
@@ -0,0 +1,361 @@
+use leptos::*;
+use crate::components::CopyButton;
+use crate::pod_builder::{EnvSource, EnvVar, Label, PodForm, Probe, ProbeKind, RestartPolicy, Volume, VolumeSource};
+
+/// The value a field starts with. Inputs are not bound to the form in both
+/// directions: each one writes its field, and the rows around it are rebuilt
+/// whenever the form changes shape, so reading the value once is enough.
+fn current<T: Clone>(form: RwSignal<PodForm>, field: impl Fn(&mut PodForm) -> Option<&mut T>) -> Option<T> {
+    form.try_update_untracked(|form| field(form).cloned()).flatten()
+}
+
+fn text_field<F>(form: RwSignal<PodForm>, label: &'static str, placeholder: &'static str, field: F) -> impl IntoView
+where
+    F: Fn(&mut PodForm) -> Option<&mut String> + Copy + 'static,
+{
+    view! {
+        <label class="context-field">
+            <span>{label}</span>
+            <input
+                type="text"
+                spellcheck="false"
+                placeholder=placeholder
+                prop:value=current(form, field).unwrap_or_default()
+                on:input=move |ev| {
+                    let value = event_target_value(&ev);
+                    form.update(|form| if let Some(field) = field(form) { *field = value });
+                }
+            />
+        </label>
+    }
+}
+
+fn select_field<T, F>(
+    form: RwSignal<PodForm>,
+    label: &'static str,
+    options: &'static [T],
+    name: fn(T) -> &'static str,
+    field: F,
+) -> impl IntoView
+where
+    T: Copy + 'static,
+    F: Fn(&mut PodForm) -> Option<&mut T> + Copy + 'static,
+{
+    let selected = current(form, field).map(name);
+    view! {
+        <label class="context-field">
+            <span>{label}</span>
+            <select on:change=move |ev| {
+                let value = event_target_value(&ev);
+                if let Some(&option) = options.iter().find(|&&option| name(option) == value) {
+                    form.update(|form| if let Some(field) = field(form) { *field = option });
+                }
+            }>
+                {options.iter().map(|&option| view! {
+                    <option value=name(option) selected=selected == Some(name(option))>{name(option)}</option>
+                }).collect_view()}
+            </select>
+        </label>
+    }
+}
+
+fn check_field<F>(form: RwSignal<PodForm>, label: &'static str, field: F) -> impl IntoView
+where
+    F: Fn(&mut PodForm) -> Option<&mut bool> + Copy + 'static,
+{
+    view! {
+        <label class="builder-check">
+            <input
+                type="checkbox"
+                prop:checked=current(form, field).unwrap_or_default()
+                on:change=move |ev| {
+                    let checked = event_target_checked(&ev);
+                    form.update(|form| if let Some(field) = field(form) { *field = checked });
+                }
+            />
+            <code>{label}</code>
+        </label>
+    }
+}
+
+/// The rows of one list in the form, each with a remove button. They are
+/// rebuilt when a row is added or removed, as indices shift.
+fn rows<V, R>(
+    form: RwSignal<PodForm>,
+    count: fn(&PodForm) -> usize,
+    remove: fn(&mut PodForm, usize),
+    row: R,
+) -> impl IntoView
+where
+    V: IntoView,
+    R: Fn(usize) -> V + Copy + 'static,
+{
+    let count = create_memo(move |_| form.with(count));
+    move || {
+        (0..count.get())
+            .map(|index| view! {
+                <div class="builder-row">
+                    {row(index)}
+                    <button
+                        class="builder-remove"
+                        title="Remove"
+                        on:click=move |_| form.update(|form| remove(form, index))
+                    >
+                        "✕"
+                    </button>
+                </div>
+            })
+            .collect_view()
+    }
+}
+
+fn probe_fields(
+    form: RwSignal<PodForm>,
+    label: &'static str,
+    probe: fn(&PodForm) -> &Probe,
+    probe_mut: fn(&mut PodForm) -> &mut Probe,
+) -> impl IntoView {
+    let kind = create_memo(move |_| form.with(|form| probe(form).kind));
+    let text = move |label, placeholder, field: fn(&mut Probe) -> &mut String| {
+        text_field(form, label, placeholder, move |form| Some(field(probe_mut(form))))
+    };
+    view! {
+        <div class="builder-row">
+            {select_field(form, label, &ProbeKind::ALL, ProbeKind::name, move |form| Some(&mut probe_mut(form).kind))}
+            {move || {
+                let check = match kind.get() {
+                    ProbeKind::None => return ().into_view(),
+                    ProbeKind::HttpGet => view! {
+                        <>
+                            {text("Path", "/healthz", |probe| &mut probe.path)}
+                            {text("Port", "80", |probe| &mut probe.port)}
+                        </>
+                    }
+                    .into_view(),
+                    ProbeKind::TcpSocket => text("Port", "80", |probe| &mut probe.port).into_view(),
+                    ProbeKind::Exec => text("Command", "cat /tmp/ready", |probe| &mut probe.command).into_view(),
+                };
+                view! {
+                    <>
+                        {check}
+                        {text("Initial delay (s)", "5", |probe| &mut probe.initial_delay_seconds)}
+                        {text("Period (s)", "10", |probe| &mut probe.period_seconds)}
+                    </>
+                }
+                .into_view()
+            }}
+        </div>
+    }
+}
+
+/// A form for a single-container Pod that writes its manifest and, when
+/// every field used has a flag, the `k run` command printing the same Pod.
+#[component]
+pub fn PodBuilder() -> impl IntoView {
+    let form = create_rw_signal(PodForm::new());
+    let yaml = create_memo(move |_| form.with(PodForm::to_yaml));
+    let command = create_memo(move |_| form.with(PodForm::run_command));
+    let problems = create_memo(move |_| form.with(PodForm::problems));
+    let yaml_ref = create_node_ref::<html::Pre>();
+    let command_ref = create_node_ref::<html::Pre>();
+
+    let labels = rows(form, |form| form.labels.len(), |form, index| { form.labels.remove(index); }, move |index| {
+        view! {
+            {text_field(form, "Key", "app", move |form| form.labels.get_mut(index).map(|label| &mut label.key))}
+            {text_field(form, "Value", "web", move |form| form.labels.get_mut(index).map(|label| &mut label.value))}
+        }
+    });
+
+    let env = rows(form, |form| form.env.len(), |form, index| { form.env.remove(index); }, move |index| {
+        let source = create_memo(move |_| form.with(|form| form.env.get(index).map(|var| var.source)));
+        let value = move |label, placeholder| {
+            text_field(form, label, placeholder, move |form| form.env.get_mut(index).map(|var| &mut var.value))
+        };
+        view! {
+            {text_field(form, "Name", "APP_MODE", move |form| form.env.get_mut(index).map(|var| &mut var.name))}
+            {select_field(form, "From", &EnvSource::ALL, EnvSource::name, move |form| {
+                form.env.get_mut(index).map(|var| &mut var.source)
+            })}
+            {move || match source.get() {
+                Some(EnvSource::Literal) | None => value("Value", "production").into_view(),
+                Some(source) => view! {
+                    <>
+                        {value(if source == EnvSource::Secret { "Secret" } else { "ConfigMap" }, "app-config")}
+                        {text_field(form, "Key", "mode", move |form| form.env.get_mut(index).map(|var| &mut var.key))}
+                    </>
+                }
+                .into_view(),
+            }}
+        }
+    });
+
+    let ports = rows(form, |form| form.ports.len(), |form, index| { form.ports.remove(index); }, move |index| {
+        text_field(form, "Container port", "80", move |form| form.ports.get_mut(index))
+    });
+
+    let volumes = rows(form, |form| form.volumes.len(), |form, index| { form.volumes.remove(index); }, move |index| {
+        let source = create_memo(move |_| form.with(|form| form.volumes.get(index).map(|volume| volume.source)));
+        view! {
+            {text_field(form, "Name", "data", move |form| form.volumes.get_mut(index).map(|volume| &mut volume.name))}
+            {select_field(form, "Type", &VolumeSource::ALL, VolumeSource::name, move |form| {
+                form.volumes.get_mut(index).map(|volume| &mut volume.source)
+            })}
+            {move || {
+                let label = match source.get()? {
+                    VolumeSource::EmptyDir => return None,
+                    VolumeSource::ConfigMap => "ConfigMap",
+                    VolumeSource::Secret => "Secret",
+                    VolumeSource::PersistentVolumeClaim => "Claim",
+                };
+                Some(text_field(form, label, "app-config", move |form| {
+                    form.volumes.get_mut(index).map(|volume| &mut volume.reference)
+                }))
+            }}
+            {text_field(form, "Mount path", "/data", move |form| {
+                form.volumes.get_mut(index).map(|volume| &mut volume.mount_path)
+            })}
+        }
+    });
+
+    let add_button = move |label: &'static str, add: fn(&mut PodForm)| view! {
+        <button class="note-button secondary" on:click=move |_| form.update(add)>{label}</button>
+    };
+
+    view! {
+        <div class="study-mode">
+            <h2 class="category-title">"🧱 Pod Builder"</h2>
+            <p class="context-hint">
+                "Fill in what the task asks for and copy the manifest, or the k run command when the Pod \
+                 needs nothing it has no flag for."
+            </p>
+            <div class="pod-builder">
+                <div class="builder-form">
+                    <fieldset class="builder-section">
+                        <legend>"Pod"</legend>
+                        <div class="builder-row">
+                            {text_field(form, "Name", "web", |form| Some(&mut form.name))}
+                            {text_field(form, "Image", "nginx:alpine", |form| Some(&mut form.image))}
+                            {select_field(form, "restartPolicy", &RestartPolicy::ALL, RestartPolicy::name, |form| {
+                                Some(&mut form.restart_policy)
+                            })}
+                        </div>
+                    </fieldset>
+
+                    <fieldset class="builder-section">
+                        <legend>"Labels"</legend>
+                        {labels}
+                        {add_button("+ Add label", |form| form.labels.push(Label::default()))}
+                    </fieldset>
+
+                    <fieldset class="builder-section">
+                        <legend>"Environment"</legend>
+                        {env}
+                        {add_button("+ Add variable", |form| form.env.push(EnvVar::default()))}
+                    </fieldset>
+
+                    <fieldset class="builder-section">
+                        <legend>"Ports"</legend>
+                        {ports}
+                        {add_button("+ Add port", |form| form.ports.push(String::new()))}
+                    </fieldset>
+
+                    <fieldset class="builder-section">
+                        <legend>"Resources"</legend>
+                        <div class="builder-row">
+                            {text_field(form, "CPU request", "100m", |form| Some(&mut form.resources.cpu_request))}
+                            {text_field(form, "Memory request", "64Mi", |form| {
+                                Some(&mut form.resources.memory_request)
+                            })}
+                        </div>
+                        <div class="builder-row">
+                            {text_field(form, "CPU limit", "500m", |form| Some(&mut form.resources.cpu_limit))}
+                            {text_field(form, "Memory limit", "128Mi", |form| Some(&mut form.resources.memory_limit))}
+                        </div>
+                    </fieldset>
+
+                    <fieldset class="builder-section">
+                        <legend>"Probes"</legend>
+                        {probe_fields(
+                            form,
+                            "readinessProbe",
+                            |form| &form.readiness_probe,
+                            |form| &mut form.readiness_probe,
+                        )}
+                        {probe_fields(
+                            form,
+                            "livenessProbe",
+                            |form| &form.liveness_probe,
+                            |form| &mut form.liveness_probe,
+                        )}
+                    </fieldset>
+
+                    <fieldset class="builder-section">
+                        <legend>"Volumes"</legend>
+                        {volumes}
+                        {add_button("+ Add volume", |form| form.volumes.push(Volume::default()))}
+                    </fieldset>
+
+                    <fieldset class="builder-section">
+                        <legend>"securityContext"</legend>
+                        <div class="builder-row">
+                            {text_field(form, "runAsUser", "1000", |form| Some(&mut form.security.run_as_user))}
+                        </div>
+                        <div class="builder-checks">
+                            {check_field(form, "runAsNonRoot", |form| Some(&mut form.security.run_as_non_root))}
+                            {check_field(form, "readOnlyRootFilesystem", |form| {
+                                Some(&mut form.security.read_only_root_filesystem)
+                            })}
+                            {check_field(form, "allowPrivilegeEscalation: false", |form| {
+                                Some(&mut form.security.no_privilege_escalation)
+                            })}
+                            {check_field(form, "capabilities.drop: [ALL]", |form| {
+                                Some(&mut form.security.drop_all_capabilities)
+                            })}
+                        </div>
+                    </fieldset>
+                </div>
+
+                <div class="builder-output">
+                    {move || {
+                        let problems = problems.get();
+                        (!problems.is_empty()).then(|| view! {
+                            <ul class="validator-result validator-problems">
+                                {problems.into_iter().map(|problem| view! { <li>{problem}</li> }).collect_view()}
+                            </ul>
+                        })
+                    }}
+                    <div class="builder-output-header">
+                        <h3>"Manifest"</h3>
+                        <CopyButton text=Signal::derive(move || yaml.get()) select_target=yaml_ref />
+                    </div>
+                    <pre class="command-block" node_ref=yaml_ref>
+                        <code>{move || yaml.get()}</code>
+                    </pre>
+                    <div class="builder-output-header">
+                        <h3>"Imperative command"</h3>
+                        {move || command.with(Result::is_ok).then(|| view! {
+                            <CopyButton
+                                text=Signal::derive(move || command.get().unwrap_or_default())
+                                select_target=command_ref
+                            />
+                        })}
+                    </div>
+                    {move || match command.get() {
+                        Ok(command) => view! {
+                            <pre class="command-block" node_ref=command_ref>
+                                <code>{command}</code>
+                            </pre>
+                        }
+                        .into_view(),
+                        Err(parts) => view! {
+                            <p class="builder-note">
+                                {format!("k run has no flags for {}: apply the manifest instead.", parts.join(", "))}
+                            </p>
+                        }
+                        .into_view(),
+                    }}
+                </div>
+            </div>
+        </div>
+    }
+}
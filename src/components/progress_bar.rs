@@ -0,0 +1,27 @@
+use leptos::*;
+use crate::progress::{ProgressCounts, Status};
+
+/// A bar split into the shares of mastered, practicing and new concepts.
+#[component]
+pub fn ProgressBar(#[prop(into)] counts: Signal<ProgressCounts>) -> impl IntoView {
+    let title = move || {
+        let counts = counts.get();
+        format!(
+            "{} mastered, {} practicing, {} new",
+            counts.mastered, counts.practicing, counts.new
+        )
+    };
+
+    view! {
+        <div class="progress-bar" title=title>
+            {[Status::Mastered, Status::Practicing].into_iter().map(|status| {
+                view! {
+                    <div
+                        class=format!("progress-segment {}", status.class())
+                        style:width=move || format!("{:.1}%", counts.get().percent(status))
+                    ></div>
+                }
+            }).collect_view()}
+        </div>
+    }
+}
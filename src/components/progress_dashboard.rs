@@ -0,0 +1,44 @@
+use leptos::*;
+use crate::components::ProgressBar;
+use crate::custom::Catalog;
+use crate::progress::{Progress, Status};
+
+/// Overall readiness across the whole catalog, and the switch that hides
+/// mastered cards.
+#[component]
+pub fn ProgressDashboard(
+    catalog: Memo<Catalog>,
+    hide_mastered: ReadSignal<bool>,
+    set_hide_mastered: WriteSignal<bool>,
+) -> impl IntoView {
+    let progress = Progress::expect();
+    let counts = Signal::derive(move || {
+        catalog.with(|catalog| progress.counts(catalog.documents.iter().map(|(_, concept)| *concept)))
+    });
+
+    view! {
+        <div class="progress-dashboard">
+            <div class="progress-summary">
+                <h3>{move || format!("📈 Readiness {:.0}%", counts.get().readiness())}</h3>
+                <div class="progress-legend">
+                    {Status::ALL.into_iter().rev().map(|status| {
+                        view! {
+                            <span class=format!("progress-count {}", status.class())>
+                                {move || format!("{} {}", counts.get().count(status), status.label().to_lowercase())}
+                            </span>
+                        }
+                    }).collect_view()}
+                </div>
+                <label class="hide-mastered">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || hide_mastered.get()
+                        on:change=move |ev| set_hide_mastered.set(event_target_checked(&ev))
+                    />
+                    "Hide mastered cards"
+                </label>
+            </div>
+            <ProgressBar counts=counts />
+        </div>
+    }
+}
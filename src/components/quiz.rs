@@ -0,0 +1,160 @@
+use leptos::*;
+use crate::ckad_data::get_ckad_concepts;
+use crate::components::CopyButton;
+use crate::quiz::{generate, Question, QuestionKind, QUESTION_COUNT};
+use crate::rng::Rng;
+use crate::routing::{navigate, Route};
+
+/// Link that reopens the quiz with the same questions.
+fn share_url(seed: u64) -> String {
+    let location = window().location();
+    format!(
+        "{}{}{}",
+        location.origin().unwrap_or_default(),
+        location.pathname().unwrap_or_default(),
+        Route::Quiz(Some(seed)).to_hash(),
+    )
+}
+
+fn new_quiz() {
+    navigate(&Route::Quiz(Some(Rng::random_seed())));
+}
+
+/// Multiple-choice quiz generated from the built-in catalog. The seed comes
+/// from the URL, so a quiz can be shared; custom concepts are left out
+/// because the person opening the link would not have them.
+#[component]
+pub fn Quiz(seed: Option<u64>) -> impl IntoView {
+    let Some(seed) = seed else {
+        return view! {
+            <div class="study-mode">
+                <h2 class="category-title">"❓ Quiz"</h2>
+                <div class="study-done">
+                    <p class="context-hint">
+                        {format!(
+                            "{QUESTION_COUNT} multiple-choice questions: pick the command for a title, \
+                             or what a command does. Share the link to give someone the same quiz."
+                        )}
+                    </p>
+                    <button class="study-button" on:click=move |_| new_quiz()>"Start quiz"</button>
+                </div>
+            </div>
+        }
+        .into_view();
+    };
+
+    let questions = store_value(generate(get_ckad_concepts(), seed, QUESTION_COUNT));
+    let total = questions.with_value(Vec::len);
+    let index = create_rw_signal(0_usize);
+    let chosen = create_rw_signal(None::<usize>);
+    let score = create_rw_signal(0_usize);
+
+    let choose = move |choice: usize| {
+        if chosen.get_untracked().is_some() {
+            return;
+        }
+        let answer = questions.with_value(|questions| questions[index.get_untracked()].answer);
+        if choice == answer {
+            score.update(|score| *score += 1);
+        }
+        chosen.set(Some(choice));
+    };
+
+    let next = move |_| {
+        chosen.set(None);
+        index.update(|index| *index += 1);
+    };
+
+    let restart = move |_| {
+        chosen.set(None);
+        score.set(0);
+        index.set(0);
+    };
+
+    let question_view = move |question: Question| {
+        let heading = match question.kind {
+            QuestionKind::Command => "Which command does this?",
+            QuestionKind::Purpose => "What does this command do?",
+        };
+        let answer = question.answer;
+        view! {
+            <div class="flashcard">
+                <span class="concept-category">{question.category.name}</span>
+                <p class="quiz-heading">{heading}</p>
+                {match question.kind {
+                    QuestionKind::Command => view! {
+                        <h3 class="concept-title">{question.prompt()}</h3>
+                    }.into_view(),
+                    QuestionKind::Purpose => view! {
+                        <pre class="command-block"><code>{question.prompt()}</code></pre>
+                    }.into_view(),
+                }}
+                <div class="quiz-choices">
+                    {question.choices.iter().enumerate().map(|(choice, &text)| {
+                        let is_command = question.kind == QuestionKind::Command;
+                        view! {
+                            <button
+                                class="quiz-choice"
+                                class:correct=move || chosen.get().is_some() && choice == answer
+                                class:wrong=move || chosen.get() == Some(choice) && choice != answer
+                                disabled=move || chosen.with(Option::is_some)
+                                on:click=move |_| choose(choice)
+                            >
+                                {if is_command {
+                                    view! { <code>{text}</code> }.into_view()
+                                } else {
+                                    text.into_view()
+                                }}
+                            </button>
+                        }
+                    }).collect_view()}
+                </div>
+                <Show when=move || chosen.with(Option::is_some)>
+                    <div class="note-actions">
+                        <a
+                            class="note-button secondary"
+                            href=Route::Concept(question.concept.id.to_string()).to_hash()
+                            target="_blank"
+                        >
+                            "Open card"
+                        </a>
+                        <button class="note-button" on:click=next>"Next"</button>
+                    </div>
+                </Show>
+            </div>
+        }
+    };
+
+    let share_ref = create_node_ref::<html::Pre>();
+    let finished = move || {
+        view! {
+            <div class="study-done">
+                <p>{move || format!("🎉 {}/{total} correct", score.get())}</p>
+                <p class="context-hint">"Share this quiz:"</p>
+                <div class="quiz-share">
+                    <pre class="command-block" node_ref=share_ref><code>{share_url(seed)}</code></pre>
+                    <CopyButton text=Signal::derive(move || share_url(seed)) select_target=share_ref />
+                </div>
+                <div class="note-actions quiz-actions">
+                    <button class="study-button" on:click=restart>"Retry"</button>
+                    <button class="study-button" on:click=move |_| new_quiz()>"New quiz"</button>
+                </div>
+            </div>
+        }
+    };
+
+    view! {
+        <div class="study-mode">
+            <h2 class="category-title">"❓ Quiz"</h2>
+            <div class="study-status">
+                <span>{move || format!("Question {}/{total}", (index.get() + 1).min(total))}</span>
+                <span>{move || format!("Score: {}", score.get())}</span>
+            </div>
+            {move || match questions.with_value(|questions| questions.get(index.get()).cloned()) {
+                Some(question) => question_view(question).into_view(),
+                None => finished().into_view(),
+            }}
+        </div>
+    }
+    .into_view()
+}
@@ -4,6 +4,10 @@ use leptos::*;
 pub fn SearchBar(
     search_query: ReadSignal<String>,
     set_search_query: WriteSignal<String>,
+    ranked_view: ReadSignal<bool>,
+    set_ranked_view: WriteSignal<bool>,
+    /// Parts of the query that could not be applied, shown as inline hints.
+    query_problems: Signal<Vec<String>>,
 ) -> impl IntoView {
     let on_input = move |ev| {
         let value = event_target_value(&ev);
@@ -20,7 +24,7 @@ pub fn SearchBar(
                 <input
                     type="text"
                     class="search-input"
-                    placeholder="Search commands, concepts, or resources..."
+                    placeholder="Search commands, concepts, or resources... (try cat:storage kind:yaml)"
                     prop:value=move || search_query.get()
                     on:input=on_input
                 />
@@ -32,18 +36,43 @@ pub fn SearchBar(
                     "✕"
                 </button>
             </div>
+            {move || {
+                let problems = query_problems.get();
+                (!problems.is_empty()).then(|| {
+                    view! {
+                        <ul class="query-hints">
+                            {problems.into_iter().map(|problem| view! { <li>{problem}</li> }).collect_view()}
+                        </ul>
+                    }
+                })
+            }}
             {move || {
                 let query = search_query.get();
                 if !query.is_empty() {
                     view! {
-                        <p class="search-info">
-                            "Searching for: " <strong>{query}</strong>
-                        </p>
-                    }
+                        <div class="search-info">
+                            <p>"Searching for: " <strong>{query}</strong></p>
+                            <label class="ranked-toggle">
+                                <input
+                                    type="checkbox"
+                                    prop:checked=move || ranked_view.get()
+                                    on:change=move |ev| set_ranked_view.set(event_target_checked(&ev))
+                                />
+                                " Show one ranked list across categories"
+                            </label>
+                        </div>
+                    }.into_view()
                 } else {
                     view! {
-                        <p class="search-info">"Browse all CKAD exam concepts below"</p>
-                    }
+                        <div class="search-info">
+                            <p>"Browse all CKAD exam concepts below"</p>
+                            <p class="search-syntax">
+                                "Filters: " <code>"title:rollback"</code> " " <code>"cat:storage"</code>
+                                " " <code>"kind:yaml"</code> " " <code>"\"dry-run=client\""</code>
+                                " " <code>"-helm"</code>
+                            </p>
+                        </div>
+                    }.into_view()
                 }
             }}
         </div>
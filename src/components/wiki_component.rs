@@ -1,63 +1,203 @@
 use leptos::*;
-use crate::ckad_data::{get_ckad_concepts, Category};
-use crate::components::{ConceptCard, SearchBar};
+use crate::components::{
+    ConceptCard, ContextPanel, CustomConceptForm, Drill, Flashcards, ManifestValidator, MockExam, ModeNav,
+    NotesToolbar, PodBuilder, ProgressBar, ProgressDashboard, Quiz, SearchBar,
+};
+use crate::search::{rank, CategoryHits, Document, Field, SearchHit, SearchIndex, SearchQuery};
+use crate::custom::CustomConcepts;
+use crate::favorites::Favorites;
+use crate::notes::Notes;
+use crate::progress::{Progress, Status};
+use crate::session::PlaceholderValues;
+use crate::routing::{
+    create_route_signal, current_filter_params, navigate, scroll_to_element, write_filter_params,
+    FilterParams, Route,
+};
 
 #[component]
 pub fn CkadWiki() -> impl IntoView {
-    let (search_query, set_search_query) = create_signal(String::new());
-    let (selected_category, set_selected_category) = create_signal(None::<String>);
+    PlaceholderValues::provide();
+    let favorites = Favorites::provide();
+    let notes = Notes::provide();
+    let custom = CustomConcepts::provide();
+    let progress = Progress::provide();
 
-    let categories = create_memo(move |_| get_ckad_concepts());
+    // The built-in catalog with the reader's own concepts merged in.
+    let catalog = custom.catalog();
 
-    let filtered_categories = create_memo(move |_| {
-        let query = search_query.get().to_lowercase();
+    // Notes are searched through their own small index, rebuilt when they change.
+    let notes_index = create_memo(move |_| {
+        notes.with(|notes| catalog.with(|catalog| {
+            SearchIndex::from_documents(
+                catalog.documents.len(),
+                notes.iter().filter_map(|(id, text)| {
+                    Some((catalog.ordinal_of(id)?, Field::Note, text.as_str()))
+                }),
+            )
+        }))
+    });
+
+    // Restore the filters from the URL, ignoring a category id that no longer exists.
+    let known_category = move |category: Option<String>| {
+        category.filter(|id| catalog.with_untracked(|catalog| catalog.categories.iter().any(|cat| cat.id == id)))
+    };
+    let initial_params = current_filter_params();
+    let initial_category = known_category(initial_params.category);
+
+    let (search_query, set_search_query) = create_signal(initial_params.query);
+    let (selected_category, set_selected_category) = create_signal(initial_category);
+
+    let route = create_route_signal();
+
+    create_effect(move |previous: Option<FilterParams>| {
+        let params = FilterParams {
+            query: search_query.get(),
+            category: selected_category.get(),
+        };
+        write_filter_params(&params, previous.as_ref());
+        params
+    });
+
+    // Back/forward restores the filters that were active for that history entry.
+    window_event_listener(ev::popstate, move |_| {
+        let params = current_filter_params();
+        set_search_query.set(params.query);
+        set_selected_category.set(known_category(params.category));
+    });
+
+    let (ranked_view, set_ranked_view) = create_signal(false);
+    let (hide_mastered, set_hide_mastered) = create_signal(false);
+
+    let parsed_query = create_memo(move |_| {
+        search_query.with(|text| catalog.with(|catalog| SearchQuery::parse(text, &catalog.categories)))
+    });
+
+    let query_problems = Signal::derive(move || {
+        parsed_query.with(|query| query.problems().iter().map(ToString::to_string).collect::<Vec<_>>())
+    });
+
+    // Every concept passing the category filter and the search query, in
+    // catalog order. Without a query all concepts match with a score of 0.
+    let search_hits = create_memo(move |_| {
         let selected = selected_category.get();
-        let cats = categories.get();
+        let hide_mastered = hide_mastered.get();
 
-        if query.is_empty() && selected.is_none() {
-            return cats;
-        }
+        parsed_query.with(|query| catalog.with(|catalog| notes_index.with(|notes_index| notes.with(|notes| {
+            let concepts = &catalog.documents;
+            let document_at = |ordinal: usize| {
+                let (category, concept) = concepts[ordinal];
+                Document {
+                    category_id: category.id,
+                    concept,
+                    note: notes.get(concept.id).map(String::as_str),
+                }
+            };
 
-        cats.into_iter()
-            .filter_map(|category| {
-                // Filter by selected category first
-                if let Some(ref selected_cat) = selected {
-                    if &category.name != selected_cat {
+            query
+                .search(&[SearchIndex::catalog(), &catalog.custom_index, notes_index], document_at)
+                .into_iter()
+                .filter_map(|(ordinal, score)| {
+                    let (category, concept) = concepts[ordinal];
+                    if selected.as_ref().is_some_and(|id| category.id != id) {
                         return None;
                     }
-                }
+                    if hide_mastered && progress.status(concept.id) == Status::Mastered {
+                        return None;
+                    }
+                    Some(SearchHit {
+                        category,
+                        concept,
+                        score,
+                        matches: query.match_spans(document_at(ordinal)),
+                    })
+                })
+                .collect::<Vec<_>>()
+        }))))
+    });
 
-                // Then filter concepts by search query
-                if !query.is_empty() {
-                    let filtered_concepts: Vec<_> = category.concepts
-                        .into_iter()
-                        .filter(|concept| {
-                            concept.title.to_lowercase().contains(&query)
-                                || concept.command.to_lowercase().contains(&query)
-                                || concept.description.as_ref()
-                                    .map(|d| d.to_lowercase().contains(&query))
-                                    .unwrap_or(false)
-                        })
+    // Hits grouped back into their categories, best match first.
+    let filtered_categories = create_memo(move |_| {
+        search_hits.with(|hits| catalog.with(|catalog| {
+            catalog
+                .categories
+                .iter()
+                .filter_map(|&category| {
+                    let mut category_hits: Vec<_> = hits
+                        .iter()
+                        .filter(|hit| std::ptr::eq(hit.category, category))
+                        .cloned()
                         .collect();
 
-                    if filtered_concepts.is_empty() {
+                    if category_hits.is_empty() {
                         return None;
                     }
+                    rank(&mut category_hits);
 
-                    Some(Category {
-                        name: category.name,
-                        concepts: filtered_concepts,
+                    Some(CategoryHits {
+                        category,
+                        hits: category_hits,
                     })
-                } else {
-                    Some(category)
+                })
+                .collect::<Vec<_>>()
+        }))
+    });
+
+    // One flat list across all categories, best match first.
+    let ranked_hits = create_memo(move |_| {
+        let mut hits = search_hits.get();
+        rank(&mut hits);
+        hits
+    });
+
+    // Starred concepts that pass the current filters, pinned above the rest.
+    let favorite_hits = create_memo(move |_| {
+        let mut hits: Vec<_> = search_hits.with(|hits| {
+            hits.iter().filter(|hit| favorites.contains(hit.concept.id)).cloned().collect()
+        });
+        rank(&mut hits);
+        hits
+    });
+
+    // Scroll to the target of a deep link. A linked card hidden by the current
+    // search or category filter would never be reached, so clear them first.
+    create_effect(move |_| {
+        match route.get() {
+            Route::Concept(id) => {
+                let is_visible = filtered_categories.with_untracked(|cats| {
+                    cats.iter().any(|cat| cat.hits.iter().any(|hit| hit.concept.id == id))
+                });
+                if !is_visible {
+                    set_search_query.set(String::new());
+                    set_selected_category.set(None);
+                    set_hide_mastered.set(false);
                 }
-            })
-            .collect::<Vec<_>>()
+                scroll_to_element(format!("concept-{id}"));
+            }
+            Route::Category(id) => scroll_to_element(format!("category-{id}")),
+            Route::Home
+            | Route::Flashcards
+            | Route::Drill
+            | Route::Exam
+            | Route::Quiz(_)
+            | Route::Validate
+            | Route::PodBuilder => {}
+        }
     });
 
+    // The study mode shown instead of the catalog, if any.
+    let study_mode = create_memo(move |_| route.with(|route| (!route.is_wiki()).then(|| route.clone())));
+    let on_exam = move || route.with(|route| matches!(route, Route::Exam));
+    // Whether the mock exam was opened yet. It is mounted on first visit only.
+    let exam_opened = create_memo(move |opened: Option<&bool>| opened.copied().unwrap_or(false) || on_exam());
+
+    let highlight_for = move |concept_id: &'static str| {
+        Signal::derive(move || route.with(|r| matches!(r, Route::Concept(id) if id == concept_id)))
+    };
+
     let reset_filters = move |_| {
         set_search_query.set(String::new());
         set_selected_category.set(None);
+        navigate(&Route::Home);
     };
 
     view! {
@@ -65,18 +205,52 @@ pub fn CkadWiki() -> impl IntoView {
             <div class="wiki-header">
                 <h1>"CKAD Exam Wiki"</h1>
                 <p class="subtitle">"Interactive guide for Certified Kubernetes Application Developer exam"</p>
+                <ModeNav route=route />
             </div>
 
-            <SearchBar search_query=search_query set_search_query=set_search_query />
+            {move || study_mode.get().map(|mode| match mode {
+                Route::Flashcards => view! { <Flashcards catalog=catalog /> }.into_view(),
+                Route::Drill => view! { <Drill catalog=catalog /> }.into_view(),
+                Route::Quiz(seed) => view! { <Quiz seed=seed /> }.into_view(),
+                Route::Validate => view! { <ManifestValidator /> }.into_view(),
+                Route::PodBuilder => view! { <PodBuilder /> }.into_view(),
+                _ => ().into_view(),
+            })}
 
-            <div class="category-filter">
-                <h3>"Filter by Category"</h3>
-                <div class="category-buttons">
-                    {move || {
-                        categories.get().iter().map(|cat| {
-                            let cat_name = cat.name.clone();
-                            let cat_name_for_click = cat.name.clone();
-                            let cat_name_display = cat.name.clone();
+            // The exam stays mounted once opened, so a running exam and its
+            // timer survive a look at the catalog or another mode.
+            <Show when=move || exam_opened.get()>
+                <div class="exam-mount" class:hidden=move || !on_exam()>
+                    <MockExam catalog=catalog />
+                </div>
+            </Show>
+
+            // The catalog stays mounted in study modes, keeping its scroll
+            // position and filters for when the reader comes back.
+            <div class="wiki-body" class:hidden=move || study_mode.with(Option::is_some)>
+
+                <SearchBar
+                    search_query=search_query
+                    set_search_query=set_search_query
+                    ranked_view=ranked_view
+                    set_ranked_view=set_ranked_view
+                    query_problems=query_problems
+                />
+
+                <ContextPanel />
+                <NotesToolbar />
+                <CustomConceptForm />
+                <ProgressDashboard
+                    catalog=catalog
+                    hide_mastered=hide_mastered
+                    set_hide_mastered=set_hide_mastered
+                />
+
+                <div class="category-filter">
+                    <h3>"Filter by Category"</h3>
+                    <div class="category-buttons">
+                        {move || catalog.with(|catalog| catalog.categories.iter().map(|cat| {
+                            let cat_id = cat.id;
 
                             view! {
                                 <button
@@ -84,67 +258,139 @@ pub fn CkadWiki() -> impl IntoView {
                                     class:active=move || {
                                         selected_category.get()
                                             .as_ref()
-                                            .map(|s| s == &cat_name)
+                                            .map(|s| s == cat_id)
                                             .unwrap_or(false)
                                     }
                                     on:click=move |_| {
                                         let is_selected = selected_category.get()
                                             .as_ref()
-                                            .map(|s| s == &cat_name_for_click)
+                                            .map(|s| s == cat_id)
                                             .unwrap_or(false);
 
                                         if is_selected {
                                             set_selected_category.set(None);
                                         } else {
-                                            set_selected_category.set(Some(cat_name_for_click.clone()));
+                                            set_selected_category.set(Some(cat_id.to_string()));
                                         }
                                     }
                                 >
-                                    {cat_name_display}
+                                    {cat.name}
                                 </button>
                             }
-                        }).collect_view()
+                        }).collect_view())}
+                    </div>
+                    {move || {
+                        if selected_category.get().is_some() || !search_query.get().is_empty() {
+                            view! {
+                                <button class="reset-button" on:click=reset_filters>
+                                    "Clear Filters"
+                                </button>
+                            }.into_view()
+                        } else {
+                            view! { <div></div> }.into_view()
+                        }
                     }}
                 </div>
-                {move || {
-                    if selected_category.get().is_some() || !search_query.get().is_empty() {
-                        view! {
-                            <button class="reset-button" on:click=reset_filters>
-                                "Clear Filters"
-                            </button>
-                        }.into_view()
-                    } else {
-                        view! { <div></div> }.into_view()
-                    }
-                }}
-            </div>
 
-            <div class="concepts-container">
-                {move || {
-                    let filtered = filtered_categories.get();
-                    if filtered.is_empty() {
-                        view! {
-                            <div class="no-results">
-                                <p>"No concepts found matching your search."</p>
-                            </div>
-                        }.into_view()
-                    } else {
-                        filtered.into_iter().map(|category| {
+                <div class="concepts-container">
+                    {move || {
+                        let hits = favorite_hits.get();
+                        (!hits.is_empty()).then(|| {
                             view! {
-                                <div class="category-section">
-                                    <h2 class="category-title">{category.name}</h2>
+                                <div class="category-section favorites-section">
+                                    <h2 class="category-title">"⭐ Favorites"</h2>
                                     <div class="concepts-grid">
-                                        {category.concepts.into_iter().map(|concept| {
+                                        {hits.into_iter().map(|hit| {
+                                            let highlighted = highlight_for(hit.concept.id);
+
                                             view! {
-                                                <ConceptCard concept=concept />
+                                                <ConceptCard
+                                                    concept=hit.concept
+                                                    highlighted=highlighted
+                                                    matches=hit.matches
+                                                    category_name=hit.category.name
+                                                    pinned=true
+                                                />
                                             }
                                         }).collect_view()}
                                     </div>
                                 </div>
                             }
-                        }).collect_view().into_view()
-                    }
-                }}
+                        })
+                    }}
+                    {move || {
+                        if ranked_view.get() && !parsed_query.with(SearchQuery::is_empty) {
+                            let hits = ranked_hits.get();
+                            if hits.is_empty() {
+                                return view! {
+                                    <div class="no-results">
+                                        <p>"No concepts found matching your search."</p>
+                                    </div>
+                                }.into_view();
+                            }
+
+                            return view! {
+                                <div class="category-section ranked-results">
+                                    <h2 class="category-title">"🔎 Ranked Results"</h2>
+                                    <div class="concepts-grid">
+                                        {hits.into_iter().map(|hit| {
+                                            let highlighted = highlight_for(hit.concept.id);
+
+                                            view! {
+                                                <ConceptCard
+                                                    concept=hit.concept
+                                                    highlighted=highlighted
+                                                    matches=hit.matches
+                                                    category_name=hit.category.name
+                                                />
+                                            }
+                                        }).collect_view()}
+                                    </div>
+                                </div>
+                            }.into_view();
+                        }
+
+                        let filtered = filtered_categories.get();
+                        if filtered.is_empty() {
+                            view! {
+                                <div class="no-results">
+                                    <p>"No concepts found matching your search."</p>
+                                </div>
+                            }.into_view()
+                        } else {
+                            filtered.into_iter().map(|CategoryHits { category, hits }| {
+                                let permalink = Route::Category(category.id.to_string()).to_hash();
+                                // Progress covers the whole category, not just the cards shown.
+                                let counts = Signal::derive(move || progress.counts(category.concepts));
+
+                                view! {
+                                    <div class="category-section" id=format!("category-{}", category.id)>
+                                        <h2 class="category-title">
+                                            <a class="category-link" href=permalink>{category.name}</a>
+                                            <span class="category-progress">
+                                                {move || format!("{}/{} mastered", counts.get().mastered, counts.get().total())}
+                                            </span>
+                                        </h2>
+                                        <ProgressBar counts=counts />
+                                        <div class="concepts-grid">
+                                            {hits.into_iter().map(|hit| {
+                                                let highlighted = highlight_for(hit.concept.id);
+
+                                                view! {
+                                                    <ConceptCard
+                                                        concept=hit.concept
+                                                        highlighted=highlighted
+                                                        matches=hit.matches
+                                                    />
+                                                }
+                                            }).collect_view()}
+                                        </div>
+                                    </div>
+                                }
+                            }).collect_view().into_view()
+                        }
+                    }}
+                </div>
             </div>
         </div>
     }
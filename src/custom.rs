@@ -0,0 +1,793 @@
+//! Concepts and categories the reader adds in the browser.
+//!
+//! They are stored in `localStorage` as a bundle, which is also the format
+//! of JSON and YAML exports. A bundle follows the schema of the built-in
+//! catalog: a `version` and a list of categories, each with an `id`, a `name`
+//! and `concepts` written exactly like `[[concepts]]` in `data/categories/`.
+//! A category that reuses a built-in id adds its concepts to that category
+//! and leaves out the name.
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use crate::catalog_rules::{check_text, is_placeholder_name, is_slug, looks_like_yaml, FRAGMENT_TYPES};
+use crate::ckad_data::{catalog_concepts, get_ckad_concepts, Category, Concept, ConceptKind};
+use crate::placeholders::occurrences;
+use crate::search::{Field, SearchIndex};
+use crate::{storage, yaml};
+
+const STORAGE_KEY: &str = "custom-concepts";
+
+pub const BUNDLE_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Bundle {
+    pub version: u32,
+    pub categories: Vec<BundleCategory>,
+}
+
+impl Default for Bundle {
+    fn default() -> Bundle {
+        Bundle {
+            version: BUNDLE_VERSION,
+            categories: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BundleCategory {
+    pub id: String,
+    /// Required for a new category, left out for a built-in one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub concepts: Vec<BundleConcept>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BundleConcept {
+    pub id: String,
+    pub title: String,
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub placeholders: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub equivalents: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fragment: Option<String>,
+}
+
+/// Where a concept saved from the form goes.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CategoryChoice {
+    Existing(String),
+    New(String),
+}
+
+impl Bundle {
+    fn parse(text: &str) -> Result<Bundle, String> {
+        let value = if text.trim_start().starts_with('{') {
+            serde_json::from_str(text).map_err(|err| format!("Invalid JSON: {err}"))?
+        } else {
+            let mut documents = yaml::parse_documents(text).map_err(|err| format!("Invalid YAML: {err}"))?;
+            if documents.len() != 1 {
+                return Err("A YAML bundle must contain exactly one document.".to_string());
+            }
+            documents.remove(0)
+        };
+        serde_json::from_value(value).map_err(|err| format!("Not a concept bundle: {err}"))
+    }
+
+    fn find_concept(&self, id: &str) -> Option<(&BundleCategory, &BundleConcept)> {
+        self.categories
+            .iter()
+            .find_map(|category| Some((category, category.concepts.iter().find(|concept| concept.id == id)?)))
+    }
+
+    fn remove_concept(&mut self, id: &str) {
+        for category in &mut self.categories {
+            category.concepts.retain(|concept| concept.id != id);
+        }
+        self.categories.retain(|category| !category.concepts.is_empty());
+    }
+
+    fn category_mut(&mut self, id: &str, name: Option<String>) -> &mut BundleCategory {
+        match self.categories.iter().position(|category| category.id == id) {
+            Some(index) => &mut self.categories[index],
+            None => {
+                self.categories.push(BundleCategory {
+                    id: id.to_string(),
+                    name,
+                    concepts: Vec::new(),
+                });
+                self.categories.last_mut().unwrap()
+            }
+        }
+    }
+
+    /// Checks the bundle against the rules of the built-in catalog. Custom ids
+    /// and titles must not clash with built-in ones or with each other.
+    fn validate(&self) -> Result<(), String> {
+        if self.version != BUNDLE_VERSION {
+            return Err(format!(
+                "Unsupported bundle version {} (expected {BUNDLE_VERSION}).",
+                self.version
+            ));
+        }
+
+        let builtin = get_ckad_concepts();
+        let mut category_ids = HashSet::new();
+        let mut concept_ids: HashSet<&str> = catalog_concepts().map(|(_, concept)| concept.id).collect();
+        let mut titles: HashSet<&str> = catalog_concepts().map(|(_, concept)| concept.title).collect();
+
+        for category in &self.categories {
+            let id = category.id.as_str();
+            if !is_slug(id) {
+                return Err(format!("Category id `{id}` must be lowercase letters, digits and dashes."));
+            }
+            if !category_ids.insert(id) {
+                return Err(format!("Category `{id}` is listed twice."));
+            }
+            let is_builtin = builtin.iter().any(|existing| existing.id == id);
+            match &category.name {
+                Some(_) if is_builtin => {
+                    return Err(format!("Category `{id}` is built in; leave out its name."));
+                }
+                Some(name) => {
+                    if let Some(problem) = check_text(name) {
+                        return Err(format!("Name of category `{id}` {problem}."));
+                    }
+                }
+                None if !is_builtin => return Err(format!("New category `{id}` needs a name.")),
+                None => {}
+            }
+
+            for concept in &category.concepts {
+                concept.validate()?;
+                if !concept_ids.insert(&concept.id) {
+                    return Err(format!("Concept id `{}` is already taken.", concept.id));
+                }
+                if !titles.insert(&concept.title) {
+                    return Err(format!("A concept titled \"{}\" already exists.", concept.title));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl BundleConcept {
+    fn validate(&self) -> Result<(), String> {
+        let id = &self.id;
+        if !is_slug(id) {
+            return Err(format!("Concept id `{id}` must be lowercase letters, digits and dashes."));
+        }
+        for (what, text) in [("title", Some(&self.title)), ("command", Some(&self.command)), ("description", self.description.as_ref())] {
+            if let Some(problem) = text.and_then(|text| check_text(text)) {
+                return Err(format!("The {what} of concept `{id}` {problem}."));
+            }
+        }
+        if let Some(kind) = &self.kind {
+            if kind != "shell" && kind != "yaml" {
+                return Err(format!("Unknown kind `{kind}` of concept `{id}` (expected `shell` or `yaml`)."));
+            }
+        }
+        if let Some(fragment) = &self.fragment {
+            if self.kind() != ConceptKind::Yaml {
+                return Err(format!("Only YAML snippets can declare a fragment type, but `{id}` is a shell command."));
+            }
+            if !FRAGMENT_TYPES.contains(&fragment.as_str()) {
+                return Err(format!(
+                    "Unknown fragment type `{fragment}` of concept `{id}` (expected one of {}).",
+                    FRAGMENT_TYPES.join(", ")
+                ));
+            }
+        }
+        for (index, name) in self.placeholders.iter().enumerate() {
+            if !is_placeholder_name(name) {
+                return Err(format!("Placeholder `{name}` must be uppercase letters, digits and underscores."));
+            }
+            if self.placeholders[..index].contains(name) {
+                return Err(format!("Placeholder `{name}` is declared twice."));
+            }
+            if occurrences(&self.command, name).is_empty() {
+                return Err(format!("Placeholder `{name}` does not appear in the command of `{id}`."));
+            }
+        }
+        for (index, equivalent) in self.equivalents.iter().enumerate() {
+            if let Some(problem) = check_text(equivalent) {
+                return Err(format!("An equivalent answer of concept `{id}` {problem}."));
+            }
+            if *equivalent == self.command {
+                return Err(format!("Equivalent answer `{equivalent}` of `{id}` is the command itself."));
+            }
+            if self.equivalents[..index].contains(equivalent) {
+                return Err(format!("Equivalent answer `{equivalent}` of `{id}` is listed twice."));
+            }
+        }
+        Ok(())
+    }
+
+    fn kind(&self) -> ConceptKind {
+        match self.kind.as_deref() {
+            Some("yaml") => ConceptKind::Yaml,
+            Some(_) => ConceptKind::Shell,
+            None if looks_like_yaml(&self.command) => ConceptKind::Yaml,
+            None => ConceptKind::Shell,
+        }
+    }
+}
+
+/// Turns `text` into a slug, e.g. "Debug with ephemeral containers" into
+/// `debug-with-ephemeral-containers`.
+pub fn slugify(text: &str) -> String {
+    let slug = text
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "custom".to_string()
+    } else {
+        slug
+    }
+}
+
+/// `base`, or `base-2`, `base-3`… whichever is not `taken`.
+fn unique_id(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    (1..)
+        .map(|n| if n == 1 { base.to_string() } else { format!("{base}-{n}") })
+        .find(|candidate| !taken(candidate))
+        .unwrap()
+}
+
+/// The built-in catalog with the custom concepts merged in.
+#[derive(PartialEq)]
+pub struct Catalog {
+    pub categories: Vec<&'static Category>,
+    /// Every concept with its category, by search ordinal: the built-in
+    /// concepts in catalog order (matching `SearchIndex::catalog()`), then
+    /// the custom ones.
+    pub documents: Vec<(&'static Category, &'static Concept)>,
+    /// Index of the custom concepts, using the same ordinals.
+    pub custom_index: SearchIndex,
+}
+
+impl Catalog {
+    pub fn ordinal_of(&self, id: &str) -> Option<u32> {
+        self.documents
+            .iter()
+            .position(|(_, concept)| concept.id == id)
+            .map(|ordinal| ordinal as u32)
+    }
+}
+
+/// Moves `text` to static memory, where the rest of the catalog lives.
+fn leak(text: &str) -> &'static str {
+    Box::leak(text.into())
+}
+
+thread_local! {
+    /// Custom concepts and categories already moved to static memory, by
+    /// content. The catalog is rebuilt on every edit or import; this way only
+    /// what changed is leaked again.
+    static LEAKED_CONCEPTS: RefCell<HashMap<BundleConcept, Concept>> = RefCell::default();
+    static LEAKED_CATEGORIES: RefCell<HashMap<BundleCategory, &'static Category>> = RefCell::default();
+}
+
+fn leak_concept(concept: &BundleConcept) -> Concept {
+    LEAKED_CONCEPTS.with(|leaked| {
+        *leaked.borrow_mut().entry(concept.clone()).or_insert_with(|| Concept {
+            id: leak(&concept.id),
+            title: leak(&concept.title),
+            command: leak(&concept.command),
+            description: concept.description.as_deref().map(leak),
+            kind: concept.kind(),
+            placeholders: Box::leak(concept.placeholders.iter().map(|name| leak(name)).collect()),
+            equivalents: Box::leak(concept.equivalents.iter().map(|text| leak(text)).collect()),
+            fragment: concept.fragment.as_deref().map(leak),
+        })
+    })
+}
+
+/// `custom` as a catalog category: a new one, or `builtin` with the custom
+/// concepts appended.
+fn leak_category(custom: &BundleCategory, builtin: Option<&'static Category>) -> &'static Category {
+    LEAKED_CATEGORIES.with(|leaked| {
+        *leaked.borrow_mut().entry(custom.clone()).or_insert_with(|| {
+            let custom_concepts = custom.concepts.iter().map(leak_concept);
+            let category = match builtin {
+                Some(category) => Category {
+                    concepts: Box::leak(category.concepts.iter().copied().chain(custom_concepts).collect()),
+                    ..*category
+                },
+                None => Category {
+                    id: leak(&custom.id),
+                    name: leak(custom.name.as_deref().unwrap_or(&custom.id)),
+                    concepts: Box::leak(custom_concepts.collect()),
+                },
+            };
+            Box::leak(Box::new(category))
+        })
+    })
+}
+
+/// Builds the merged catalog. Categories and concepts are moved into static
+/// memory so the rest of the app can borrow them like the built-in ones, once
+/// for each distinct category and concept the reader writes.
+fn merge(bundle: &Bundle) -> Catalog {
+    let builtin = get_ckad_concepts();
+
+    let mut categories: Vec<&'static Category> = builtin
+        .iter()
+        .map(|category| match bundle.categories.iter().find(|custom| custom.id == category.id) {
+            Some(custom) => leak_category(custom, Some(category)),
+            None => category,
+        })
+        .collect();
+    for custom in &bundle.categories {
+        if !builtin.iter().any(|category| category.id == custom.id) {
+            categories.push(leak_category(custom, None));
+        }
+    }
+
+    // Built-in concepts keep the ordinals of the static index.
+    let builtin_count = |category: &Category| {
+        builtin
+            .iter()
+            .find(|existing| existing.id == category.id)
+            .map_or(0, |existing| existing.concepts.len())
+    };
+    let mut documents = Vec::new();
+    for &category in &categories {
+        documents.extend(category.concepts[..builtin_count(category)].iter().map(|concept| (category, concept)));
+    }
+    let first_custom = documents.len();
+    for &category in &categories {
+        documents.extend(category.concepts[builtin_count(category)..].iter().map(|concept| (category, concept)));
+    }
+
+    let mut texts = Vec::new();
+    for (ordinal, (_, concept)) in documents.iter().enumerate().skip(first_custom) {
+        let ordinal = ordinal as u32;
+        texts.push((ordinal, Field::Title, concept.title));
+        if let Some(description) = concept.description {
+            texts.push((ordinal, Field::Description, description));
+        }
+        texts.push((ordinal, Field::Command, concept.command));
+    }
+    let custom_index = SearchIndex::from_documents(documents.len(), texts);
+
+    Catalog {
+        categories,
+        documents,
+        custom_index,
+    }
+}
+
+/// The reader's custom concepts, persisted in `localStorage`, and the id of
+/// the one being edited in the form, if any.
+#[derive(Clone, Copy)]
+pub struct CustomConcepts {
+    bundle: RwSignal<Bundle>,
+    editing: RwSignal<Option<String>>,
+    /// Why the stored bundle could not be loaded. While set, the stored text
+    /// is kept as it is and no change is saved over it.
+    load_error: RwSignal<Option<String>>,
+}
+
+impl CustomConcepts {
+    /// Loads the custom concepts and makes them available to child
+    /// components. A stored bundle that does not parse or no longer
+    /// validates (say, because a built-in concept took one of its ids) is
+    /// left out of the catalog, but never overwritten: the reader can export
+    /// it to fix it, or discard it.
+    pub fn provide() -> CustomConcepts {
+        let stored = storage::load_text(STORAGE_KEY)
+            .map(|text| Bundle::parse(&text).and_then(|bundle| bundle.validate().map(|()| bundle)));
+        let (bundle, load_error) = match stored {
+            Some(Ok(bundle)) => (bundle, None),
+            Some(Err(error)) => (Bundle::default(), Some(error)),
+            None => (Bundle::default(), None),
+        };
+        let custom = CustomConcepts {
+            bundle: create_rw_signal(bundle),
+            editing: create_rw_signal(None),
+            load_error: create_rw_signal(load_error),
+        };
+        provide_context(custom);
+        custom
+    }
+
+    pub fn load_error(&self) -> Option<String> {
+        self.load_error.get()
+    }
+
+    /// Refuses changes while the stored bundle failed to load, as saving
+    /// them would replace it.
+    fn ensure_loaded(&self) -> Result<(), String> {
+        match self.load_error.get_untracked() {
+            Some(_) => Err("Your saved custom concepts could not be loaded; export or discard them first.".to_string()),
+            None => Ok(()),
+        }
+    }
+
+    /// Drops a stored bundle that failed to load, so that new concepts can be
+    /// saved.
+    pub fn discard_stored(&self) {
+        self.load_error.set(None);
+        self.commit(Bundle::default());
+    }
+
+    pub fn expect() -> CustomConcepts {
+        expect_context::<CustomConcepts>()
+    }
+
+    /// The merged catalog, rebuilt whenever the custom concepts change.
+    pub fn catalog(&self) -> Memo<Catalog> {
+        let bundle = self.bundle;
+        create_memo(move |_| bundle.with(merge))
+    }
+
+    pub fn count(&self) -> usize {
+        self.bundle.with(|bundle| bundle.categories.iter().map(|category| category.concepts.len()).sum())
+    }
+
+    pub fn is_custom(&self, id: &str) -> bool {
+        self.bundle.with(|bundle| bundle.find_concept(id).is_some())
+    }
+
+    /// Custom categories as `(id, name)`, for the form's category picker.
+    pub fn new_categories(&self) -> Vec<(String, String)> {
+        self.bundle.with(|bundle| {
+            bundle
+                .categories
+                .iter()
+                .filter_map(|category| Some((category.id.clone(), category.name.clone()?)))
+                .collect()
+        })
+    }
+
+    /// The concept being edited and the id of its category. Only tracks which
+    /// concept is picked, so saving other changes does not reload the form.
+    pub fn editing(&self) -> Option<(String, BundleConcept)> {
+        let id = self.editing.get()?;
+        self.bundle.with_untracked(|bundle| {
+            bundle
+                .find_concept(&id)
+                .map(|(category, concept)| (category.id.clone(), concept.clone()))
+        })
+    }
+
+    pub fn start_editing(&self, id: &str) {
+        self.editing.set(Some(id.to_string()));
+    }
+
+    pub fn stop_editing(&self) {
+        self.editing.set(None);
+    }
+
+    /// Adds a concept, or replaces the one being edited. The id of a new
+    /// concept is derived from its title; an edited concept keeps its id so
+    /// favorites and notes stay attached.
+    pub fn save(&self, mut concept: BundleConcept, category: CategoryChoice) -> Result<(), String> {
+        self.ensure_loaded()?;
+        let editing = self.editing.get_untracked();
+        let mut bundle = self.bundle.get_untracked();
+        if let Some(id) = &editing {
+            bundle.remove_concept(id);
+        }
+
+        let taken_concept = |bundle: &Bundle, id: &str| {
+            bundle.find_concept(id).is_some() || catalog_concepts().any(|(_, concept)| concept.id == id)
+        };
+        concept.id = match editing {
+            Some(id) => id,
+            None => unique_id(&slugify(&concept.title), |id| taken_concept(&bundle, id)),
+        };
+
+        let (category_id, name) = match category {
+            CategoryChoice::Existing(id) => (id, None),
+            CategoryChoice::New(name) => {
+                let taken_category = |id: &str| {
+                    get_ckad_concepts().iter().any(|category| category.id == id)
+                        || bundle.categories.iter().any(|category| category.id == id)
+                };
+                (unique_id(&slugify(&name), taken_category), Some(name.trim().to_string()))
+            }
+        };
+        bundle.category_mut(&category_id, name).concepts.push(concept);
+
+        bundle.validate()?;
+        self.commit(bundle);
+        self.editing.set(None);
+        Ok(())
+    }
+
+    pub fn delete(&self, id: &str) {
+        if self.ensure_loaded().is_err() {
+            return;
+        }
+        let mut bundle = self.bundle.get_untracked();
+        bundle.remove_concept(id);
+        self.commit(bundle);
+        if self.editing.get_untracked().as_deref() == Some(id) {
+            self.editing.set(None);
+        }
+    }
+
+    /// Merges a JSON or YAML bundle into the custom concepts; a concept in
+    /// the file replaces the custom concept with the same id. Returns the
+    /// number of concepts imported.
+    pub fn import(&self, text: &str) -> Result<usize, String> {
+        self.ensure_loaded()?;
+        let imported = Bundle::parse(text)?;
+        if imported.version != BUNDLE_VERSION {
+            return Err(format!(
+                "Unsupported bundle version {} (expected {BUNDLE_VERSION}).",
+                imported.version
+            ));
+        }
+
+        let mut bundle = self.bundle.get_untracked();
+        let mut count = 0;
+        for category in imported.categories {
+            for concept in &category.concepts {
+                bundle.remove_concept(&concept.id);
+            }
+            count += category.concepts.len();
+            bundle
+                .category_mut(&category.id, category.name)
+                .concepts
+                .extend(category.concepts);
+        }
+
+        bundle.validate()?;
+        self.commit(bundle);
+        Ok(count)
+    }
+
+    /// The stored text of a bundle that failed to load, which is what the
+    /// exports return then. It is JSON, so also valid YAML.
+    fn unloaded_text(&self) -> Option<String> {
+        self.load_error.get_untracked()?;
+        storage::load_text(STORAGE_KEY)
+    }
+
+    pub fn export_json(&self) -> String {
+        if let Some(text) = self.unloaded_text() {
+            return text;
+        }
+        self.bundle.with_untracked(|bundle| serde_json::to_string_pretty(bundle).unwrap_or_default())
+    }
+
+    pub fn export_yaml(&self) -> String {
+        if let Some(text) = self.unloaded_text() {
+            return text;
+        }
+        self.bundle.with_untracked(|bundle| {
+            serde_json::to_value(bundle)
+                .map(|value| yaml::emit(&value))
+                .unwrap_or_default()
+        })
+    }
+
+    fn commit(&self, bundle: Bundle) {
+        storage::save(STORAGE_KEY, &bundle);
+        self.bundle.set(bundle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::{Document, SearchQuery};
+
+    fn concept(id: &str, title: &str, command: &str) -> BundleConcept {
+        BundleConcept {
+            id: id.to_string(),
+            title: title.to_string(),
+            command: command.to_string(),
+            description: None,
+            kind: None,
+            placeholders: Vec::new(),
+            equivalents: Vec::new(),
+            fragment: None,
+        }
+    }
+
+    fn category(id: &str, name: Option<&str>, concepts: Vec<BundleConcept>) -> BundleCategory {
+        BundleCategory { id: id.to_string(), name: name.map(str::to_string), concepts }
+    }
+
+    fn bundle(categories: Vec<BundleCategory>) -> Bundle {
+        Bundle { version: BUNDLE_VERSION, categories }
+    }
+
+    fn builtin_category() -> &'static Category {
+        &get_ckad_concepts()[0]
+    }
+
+    /// A bundle adding one concept to a built-in category and one to a new
+    /// category.
+    fn sample() -> Bundle {
+        bundle(vec![
+            category(
+                builtin_category().id,
+                None,
+                vec![concept("ephemeral-debug", "Debug with an ephemeral container", "k debug POD --image=busybox")],
+            ),
+            category("my-drills", Some("🧪 My Drills"), vec![concept("skeleton", "Skeleton", "k create deploy x")]),
+        ])
+    }
+
+    fn validate_one(concept: BundleConcept) -> Result<(), String> {
+        bundle(vec![category("my-drills", Some("My Drills"), vec![concept])]).validate()
+    }
+
+    #[test]
+    fn valid_bundles_pass() {
+        assert_eq!(sample().validate(), Ok(()));
+        assert_eq!(Bundle::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn ids_and_titles_must_not_clash_with_builtin_concepts() {
+        let builtin = builtin_category().concepts[0];
+        assert_eq!(
+            validate_one(concept(builtin.id, "Something new", "k get pods")),
+            Err(format!("Concept id `{}` is already taken.", builtin.id))
+        );
+        assert_eq!(
+            validate_one(concept("something-new", builtin.title, "k get pods")),
+            Err(format!("A concept titled \"{}\" already exists.", builtin.title))
+        );
+    }
+
+    #[test]
+    fn ids_and_titles_must_not_clash_with_each_other() {
+        let mut duplicate_id = sample();
+        duplicate_id.categories[1].concepts.push(concept("skeleton", "Another", "k get pods"));
+        assert_eq!(duplicate_id.validate(), Err("Concept id `skeleton` is already taken.".to_string()));
+
+        let mut duplicate_title = sample();
+        duplicate_title.categories[1].concepts.push(concept("another", "Skeleton", "k get pods"));
+        assert_eq!(duplicate_title.validate(), Err("A concept titled \"Skeleton\" already exists.".to_string()));
+    }
+
+    #[test]
+    fn only_new_categories_have_names() {
+        let builtin_id = builtin_category().id;
+        let named_builtin = bundle(vec![category(builtin_id, Some("Renamed"), vec![])]);
+        assert_eq!(named_builtin.validate(), Err(format!("Category `{builtin_id}` is built in; leave out its name.")));
+
+        let unnamed = bundle(vec![category("my-drills", None, vec![])]);
+        assert_eq!(unnamed.validate(), Err("New category `my-drills` needs a name.".to_string()));
+
+        let twice = bundle(vec![category("a", Some("A"), vec![]), category("a", Some("A"), vec![])]);
+        assert_eq!(twice.validate(), Err("Category `a` is listed twice.".to_string()));
+    }
+
+    #[test]
+    fn other_versions_are_rejected() {
+        let future = Bundle { version: BUNDLE_VERSION + 1, ..Bundle::default() };
+        assert!(future.validate().unwrap_err().starts_with("Unsupported bundle version"));
+    }
+
+    #[test]
+    fn placeholders_must_appear_once_in_the_command() {
+        let with_placeholders = |names: &[&str]| BundleConcept {
+            placeholders: names.iter().map(|name| name.to_string()).collect(),
+            ..concept("logs", "Logs", "k logs POD")
+        };
+        assert_eq!(validate_one(with_placeholders(&["POD"])), Ok(()));
+        assert_eq!(
+            validate_one(with_placeholders(&["POD", "POD"])),
+            Err("Placeholder `POD` is declared twice.".to_string())
+        );
+        assert_eq!(
+            validate_one(with_placeholders(&["NAMESPACE"])),
+            Err("Placeholder `NAMESPACE` does not appear in the command of `logs`.".to_string())
+        );
+    }
+
+    #[test]
+    fn equivalents_differ_from_the_command_and_each_other() {
+        let with_equivalents = |equivalents: &[&str]| BundleConcept {
+            equivalents: equivalents.iter().map(|text| text.to_string()).collect(),
+            ..concept("logs", "Logs", "k logs web")
+        };
+        assert_eq!(validate_one(with_equivalents(&["kubectl logs web"])), Ok(()));
+        assert_eq!(
+            validate_one(with_equivalents(&["k logs web"])),
+            Err("Equivalent answer `k logs web` of `logs` is the command itself.".to_string())
+        );
+        assert_eq!(
+            validate_one(with_equivalents(&["kubectl logs web", "kubectl logs web"])),
+            Err("Equivalent answer `kubectl logs web` of `logs` is listed twice.".to_string())
+        );
+    }
+
+    #[test]
+    fn fragment_types_are_kept_and_checked() {
+        let text = r#"{"version": 1, "categories": [{"id": "my-drills", "name": "My Drills", "concepts": [
+            {"id": "probe", "title": "Probe", "command": "readinessProbe:\n  periodSeconds: 5", "fragment": "container"}
+        ]}]}"#;
+        let parsed = Bundle::parse(text).unwrap();
+        assert_eq!(parsed.validate(), Ok(()));
+        let catalog = merge(&parsed);
+        assert_eq!(catalog.documents.last().unwrap().1.fragment, Some("container"));
+
+        let with_fragment = |command: &str, fragment: &str| BundleConcept {
+            fragment: Some(fragment.to_string()),
+            ..concept("probe", "Probe", command)
+        };
+        assert_eq!(
+            validate_one(with_fragment("k get pods", "container")),
+            Err("Only YAML snippets can declare a fragment type, but `probe` is a shell command.".to_string())
+        );
+        assert!(validate_one(with_fragment("periodSeconds: 5", "Probe"))
+            .unwrap_err()
+            .starts_with("Unknown fragment type `Probe` of concept `probe` (expected one of container, podSpec"));
+    }
+
+    #[test]
+    fn merge_appends_custom_concepts_after_the_builtin_ones() {
+        let catalog = merge(&sample());
+        let builtin_count = catalog_concepts().count();
+        assert_eq!(catalog.documents.len(), builtin_count + 2);
+        assert_eq!(catalog.categories.len(), get_ckad_concepts().len() + 1);
+
+        // Built-in concepts keep the ordinals of the static index.
+        for (ordinal, (_, concept)) in catalog_concepts().enumerate() {
+            assert_eq!(catalog.documents[ordinal].1.id, concept.id);
+        }
+
+        let extended = catalog.categories[0];
+        assert_eq!(extended.id, builtin_category().id);
+        assert_eq!(extended.concepts.last().unwrap().id, "ephemeral-debug");
+        assert_eq!(catalog.ordinal_of("ephemeral-debug"), Some(builtin_count as u32));
+
+        let new = catalog.categories.last().unwrap();
+        assert_eq!((new.id, new.name), ("my-drills", "🧪 My Drills"));
+        assert_eq!(catalog.ordinal_of("skeleton"), Some(builtin_count as u32 + 1));
+    }
+
+    #[test]
+    fn custom_concepts_are_searchable() {
+        let catalog = merge(&sample());
+        let query = SearchQuery::parse("ephemeral busybox", &catalog.categories);
+        let document_at = |ordinal: usize| {
+            let (category, concept) = catalog.documents[ordinal];
+            Document { category_id: category.id, concept, note: None }
+        };
+        let hits = query.search(&[SearchIndex::catalog(), &catalog.custom_index], document_at);
+        let ids: Vec<_> = hits.iter().map(|&(ordinal, _)| catalog.documents[ordinal].1.id).collect();
+        assert!(ids.contains(&"ephemeral-debug"), "{ids:?}");
+    }
+
+    #[test]
+    fn merging_again_reuses_unchanged_categories() {
+        let first = merge(&sample());
+        let second = merge(&sample());
+        for (a, b) in first.categories.iter().zip(&second.categories) {
+            assert!(std::ptr::eq(*a, *b));
+        }
+
+        let mut edited = sample();
+        edited.categories[1].concepts[0].title = "Deployment skeleton".to_string();
+        let third = merge(&edited);
+        assert!(std::ptr::eq(first.categories[0], third.categories[0]));
+        assert!(!std::ptr::eq(*first.categories.last().unwrap(), *third.categories.last().unwrap()));
+    }
+}
@@ -0,0 +1,59 @@
+//! The domains of the CKAD curriculum and their share of the exam score.
+//!
+//! `build.rs` includes this file to validate the domains of exam tasks, so it
+//! must not depend on the rest of the crate.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Domain {
+    DesignBuild,
+    Deployment,
+    Observability,
+    EnvironmentSecurity,
+    Networking,
+}
+
+impl Domain {
+    pub const ALL: [Domain; 5] = [
+        Domain::DesignBuild,
+        Domain::Deployment,
+        Domain::Observability,
+        Domain::EnvironmentSecurity,
+        Domain::Networking,
+    ];
+
+    /// The id used in `data/exam-tasks.toml`.
+    pub fn slug(self) -> &'static str {
+        match self {
+            Domain::DesignBuild => "design-build",
+            Domain::Deployment => "deployment",
+            Domain::Observability => "observability",
+            Domain::EnvironmentSecurity => "environment-security",
+            Domain::Networking => "networking",
+        }
+    }
+
+    pub fn from_slug(slug: &str) -> Option<Domain> {
+        Domain::ALL.into_iter().find(|domain| domain.slug() == slug)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Domain::DesignBuild => "Application Design and Build",
+            Domain::Deployment => "Application Deployment",
+            Domain::Observability => "Application Observability and Maintenance",
+            Domain::EnvironmentSecurity => "Application Environment, Configuration and Security",
+            Domain::Networking => "Services and Networking",
+        }
+    }
+
+    /// Share of the exam in percent. The shares add up to 100.
+    pub fn weight(self) -> u32 {
+        match self {
+            Domain::DesignBuild => 20,
+            Domain::Deployment => 20,
+            Domain::Observability => 15,
+            Domain::EnvironmentSecurity => 25,
+            Domain::Networking => 20,
+        }
+    }
+}
@@ -0,0 +1,522 @@
+//! Checking a typed command against the catalog's answer.
+//!
+//! Both sides are split into shell words and normalized before comparing, so
+//! that answers differing only in ways the shell or the tool ignores are
+//! accepted:
+//!
+//! - `kubectl` and its usual alias `k` are the same program, and `sudo` is
+//!   ignored;
+//! - flags may come in any order, and `--flag=value` equals `--flag value`;
+//! - short flags may carry their value attached, so `-oyaml` equals `-o yaml`,
+//!   and switches may be bundled, so `-it` equals `-i -t`;
+//! - `--output`, `--namespace` and friends equal their short forms;
+//! - quoting is ignored and `# comments` are dropped.
+//!
+//! Placeholders such as `POD_NAME` in the expected command match any value.
+//! Which flags take a value is looked up in the linter's tables, per
+//! subcommand.
+
+use crate::lint;
+use crate::placeholders::occurrences;
+use crate::shell::{program_family, split_short_flag, split_words, Word};
+use std::fmt;
+
+/// Long flags with a short form, per program, normalized to the short form.
+const ALIASES: &[(&str, &str, &str)] = &[
+    ("kubectl", "--output", "-o"),
+    ("kubectl", "--namespace", "-n"),
+    ("kubectl", "--all-namespaces", "-A"),
+    ("kubectl", "--stdin", "-i"),
+    ("kubectl", "--tty", "-t"),
+    ("kubectl", "--container", "-c"),
+    ("helm", "--namespace", "-n"),
+    ("helm", "--values", "-f"),
+    ("docker", "--detach", "-d"),
+    ("docker", "--interactive", "-i"),
+    ("docker", "--tty", "-t"),
+    ("docker", "--env", "-e"),
+    ("docker", "--publish", "-p"),
+    ("docker", "--volume", "-v"),
+];
+
+/// A flag with its value, if it takes one.
+#[derive(Clone, Debug, PartialEq)]
+struct Flag {
+    name: String,
+    value: Option<String>,
+}
+
+/// `text` as it would be typed: quoted when it contains spaces or is empty.
+fn quoted(text: &str) -> String {
+    if text.is_empty() || text.contains(char::is_whitespace) {
+        format!("\"{text}\"")
+    } else {
+        text.to_string()
+    }
+}
+
+impl fmt::Display for Flag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            Some(value) if self.name.starts_with("--") => write!(f, "{}={}", self.name, quoted(value)),
+            Some(value) => write!(f, "{} {}", self.name, quoted(value)),
+            None => f.write_str(&self.name),
+        }
+    }
+}
+
+/// One program invocation, normalized.
+#[derive(Clone, Debug, PartialEq)]
+struct Invocation {
+    /// The program and its positional arguments, in order.
+    words: Vec<String>,
+    /// Flags, whose order does not matter.
+    flags: Vec<Flag>,
+    /// Everything after `--`, passed on verbatim.
+    trailing: Vec<String>,
+    /// The operator that ends the invocation, if any.
+    operator: Option<&'static str>,
+}
+
+/// Whether `flag` is known not to take a value, after the program and
+/// positional `words` seen so far. Flags the lint tables lack may take one.
+fn is_switch(family: &str, words: &[String], flag: &str) -> bool {
+    lint::takes_value(family, &words[1..], flag) == Some(false)
+}
+
+fn alias(program: &str, flag: &str) -> String {
+    ALIASES
+        .iter()
+        .find(|(alias_program, long, _)| *alias_program == program && *long == flag)
+        .map_or(flag, |(_, _, short)| short)
+        .to_string()
+}
+
+fn parse_invocation(words: Vec<String>, operator: Option<&'static str>) -> Invocation {
+    let mut invocation = Invocation {
+        words: Vec::new(),
+        flags: Vec::new(),
+        trailing: Vec::new(),
+        operator,
+    };
+    let mut words = words.into_iter().peekable();
+    // `sudo` does not change what the command does.
+    while words.peek().is_some_and(|word| word == "sudo") {
+        words.next();
+    }
+    let Some(program) = words.next() else {
+        return invocation;
+    };
+    let Some(family) = program_family(&program) else {
+        // Unknown programs keep their words in order.
+        invocation.words.push(program);
+        invocation.words.extend(words);
+        return invocation;
+    };
+    invocation.words.push(match family {
+        "kubectl" => "k".to_string(),
+        _ => program,
+    });
+
+    while let Some(word) = words.next() {
+        if word == "--" {
+            invocation.trailing.extend(words.by_ref());
+            break;
+        }
+
+        if let Some(long) = word.strip_prefix("--").filter(|long| !long.is_empty()) {
+            let flag = match long.split_once('=') {
+                Some((name, value)) => Flag {
+                    name: alias(family, &format!("--{name}")),
+                    value: Some(value.to_string()),
+                },
+                None => {
+                    let takes_value = !is_switch(family, &invocation.words, &word)
+                        && words.peek().is_some_and(|next| !next.starts_with('-'));
+                    Flag {
+                        name: alias(family, &word),
+                        value: if takes_value { words.next() } else { None },
+                    }
+                }
+            };
+            invocation.flags.push(flag);
+        } else if word.len() > 1 && word.starts_with('-') && !word[1..].starts_with(|c: char| c.is_ascii_digit()) {
+            let letters: Vec<String> = word[1..].chars().map(|c| format!("-{c}")).collect();
+            if letters.iter().all(|letter| is_switch(family, &invocation.words, letter)) {
+                // Bundled switches such as `-it`.
+                invocation.flags.extend(letters.into_iter().map(|name| Flag { name, value: None }));
+            } else if split_short_flag(&word).1.is_empty() {
+                let takes_value = words.peek().is_some_and(|next| !next.starts_with('-') || next.len() == 1);
+                invocation.flags.push(Flag {
+                    name: word,
+                    value: if takes_value { words.next() } else { None },
+                });
+            } else {
+                // A value attached to a short flag, as in `-oyaml` or `-o=yaml`.
+                let (name, value) = split_short_flag(&word);
+                invocation.flags.push(Flag {
+                    name: name.to_string(),
+                    value: Some(value.strip_prefix('=').unwrap_or(value).to_string()),
+                });
+            }
+        } else {
+            invocation.words.push(word);
+        }
+    }
+
+    invocation
+}
+
+fn parse(command: &str) -> Vec<Invocation> {
+    let mut invocations = Vec::new();
+    let mut words = Vec::new();
+    for word in split_words(command) {
+        match word {
+            Word::Text(text) => words.push(text),
+            Word::Operator(operator) => invocations.push(parse_invocation(std::mem::take(&mut words), Some(operator))),
+        }
+    }
+    if !words.is_empty() || invocations.is_empty() {
+        invocations.push(parse_invocation(words, None));
+    }
+    invocations
+}
+
+/// Whether a command can be drilled by typing it on one line: a single
+/// statement without a heredoc.
+pub fn is_single_line(command: &str) -> bool {
+    !command.contains("<<") && !split_words(command).contains(&Word::Operator(";"))
+}
+
+/// Whether the typed `actual` text matches `expected`, where the placeholders
+/// in `expected` stand for any non-empty text.
+fn text_matches(expected: &str, actual: &str, placeholders: &[&str]) -> bool {
+    let mut slots: Vec<_> = placeholders
+        .iter()
+        .flat_map(|name| occurrences(expected, name))
+        .collect();
+    if slots.is_empty() {
+        return expected == actual;
+    }
+    slots.sort_by_key(|slot| slot.start);
+
+    // Literal pieces between the placeholders must appear in order.
+    let mut pieces = Vec::with_capacity(slots.len() + 1);
+    let mut position = 0;
+    for slot in &slots {
+        pieces.push(&expected[position..slot.start.max(position)]);
+        position = slot.end.max(position);
+    }
+    pieces.push(&expected[position..]);
+
+    let (first, rest) = pieces.split_first().unwrap();
+    let Some(mut remaining) = actual.strip_prefix(first) else {
+        return false;
+    };
+    let (last, middle) = rest.split_last().unwrap();
+    for piece in middle {
+        // Each placeholder needs at least one character.
+        let Some(found) = remaining.get(1..).and_then(|tail| tail.find(piece)) else {
+            return false;
+        };
+        remaining = &remaining[found + 1 + piece.len()..];
+    }
+    remaining.len() > last.len() && remaining.ends_with(last)
+}
+
+fn flag_matches(expected: &Flag, actual: &Flag, placeholders: &[&str]) -> bool {
+    expected.name == actual.name
+        && match (&expected.value, &actual.value) {
+            (Some(expected), Some(actual)) => text_matches(expected, actual, placeholders),
+            (None, None) => true,
+            _ => false,
+        }
+}
+
+/// How a token of the diff compares.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Change {
+    Same,
+    /// In the expected command only.
+    Missing,
+    /// In the typed answer only.
+    Extra,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiffToken {
+    pub text: String,
+    pub change: Change,
+}
+
+/// Positional words aligned with a longest common subsequence.
+fn diff_words(expected: &[String], actual: &[String], placeholders: &[&str], out: &mut Vec<DiffToken>) {
+    let (n, m) = (expected.len(), actual.len());
+    let mut lengths = vec![vec![0_usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if text_matches(&expected[i], &actual[j], placeholders) {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let token = |text: &str, change| DiffToken { text: quoted(text), change };
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if text_matches(&expected[i], &actual[j], placeholders) {
+            out.push(token(&actual[j], Change::Same));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            out.push(token(&expected[i], Change::Missing));
+            i += 1;
+        } else {
+            out.push(token(&actual[j], Change::Extra));
+            j += 1;
+        }
+    }
+    out.extend(expected[i..].iter().map(|text| token(text, Change::Missing)));
+    out.extend(actual[j..].iter().map(|text| token(text, Change::Extra)));
+}
+
+/// Flags matched regardless of order: expected flags in their order, each
+/// either found or missing, then the typed flags nothing asked for.
+fn diff_flags(expected: &[Flag], actual: &[Flag], placeholders: &[&str], out: &mut Vec<DiffToken>) {
+    let mut used = vec![false; actual.len()];
+    for flag in expected {
+        let found = actual
+            .iter()
+            .enumerate()
+            .position(|(index, candidate)| !used[index] && flag_matches(flag, candidate, placeholders));
+        match found {
+            Some(index) => {
+                used[index] = true;
+                out.push(DiffToken { text: actual[index].to_string(), change: Change::Same });
+            }
+            None => out.push(DiffToken { text: flag.to_string(), change: Change::Missing }),
+        }
+    }
+    for (flag, used) in actual.iter().zip(used) {
+        if !used {
+            out.push(DiffToken { text: flag.to_string(), change: Change::Extra });
+        }
+    }
+}
+
+/// A token-level diff of the typed `answer` against `expected`, both
+/// normalized. Flags are listed after the positional words of their
+/// invocation.
+pub fn diff(expected: &str, answer: &str, placeholders: &[&str]) -> Vec<DiffToken> {
+    let expected = parse(expected);
+    let actual = parse(answer);
+    let empty = Invocation {
+        words: Vec::new(),
+        flags: Vec::new(),
+        trailing: Vec::new(),
+        operator: None,
+    };
+
+    let mut out = Vec::new();
+    for index in 0..expected.len().max(actual.len()) {
+        let expected = expected.get(index).unwrap_or(&empty);
+        let actual = actual.get(index).unwrap_or(&empty);
+        diff_words(&expected.words, &actual.words, placeholders, &mut out);
+        diff_flags(&expected.flags, &actual.flags, placeholders, &mut out);
+        if !expected.trailing.is_empty() || !actual.trailing.is_empty() {
+            out.push(DiffToken { text: "--".to_string(), change: Change::Same });
+            diff_words(&expected.trailing, &actual.trailing, placeholders, &mut out);
+        }
+        let operators = [(expected.operator, Change::Missing), (actual.operator, Change::Extra)];
+        if expected.operator == actual.operator {
+            if let Some(operator) = expected.operator {
+                out.push(DiffToken { text: operator.to_string(), change: Change::Same });
+            }
+        } else {
+            for (operator, change) in operators {
+                if let Some(operator) = operator {
+                    out.push(DiffToken { text: operator.to_string(), change });
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Whether `answer` is equivalent to `expected` once both are normalized.
+pub fn matches(expected: &str, answer: &str, placeholders: &[&str]) -> bool {
+    diff(expected, answer, placeholders).iter().all(|token| token.change == Change::Same)
+}
+
+/// The outcome of checking an answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Verdict {
+    pub correct: bool,
+    /// The answer against the canonical command.
+    pub diff: Vec<DiffToken>,
+}
+
+/// Checks `answer` against the canonical `command` and the `equivalents`
+/// also accepted for it.
+pub fn check(answer: &str, command: &str, equivalents: &[&str], placeholders: &[&str]) -> Verdict {
+    let diff = diff(command, answer, placeholders);
+    let correct = diff.iter().all(|token| token.change == Change::Same)
+        || equivalents.iter().any(|equivalent| matches(equivalent, answer, placeholders));
+    Verdict { correct, diff }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accepts(expected: &str, answer: &str) -> bool {
+        check(answer, expected, &[], &[]).correct
+    }
+
+    fn flag(name: &str, value: Option<&str>) -> Flag {
+        Flag { name: name.to_string(), value: value.map(str::to_string) }
+    }
+
+    #[test]
+    fn kubectl_and_k_are_the_same_program() {
+        assert!(accepts("k get pods", "kubectl get pods"));
+        assert!(accepts("kubectl get pods", "k get pods"));
+        assert!(accepts("k get pods", "sudo kubectl get pods"));
+        assert!(!accepts("k get pods", "helm get pods"));
+    }
+
+    #[test]
+    fn flags_may_come_in_any_order() {
+        assert!(accepts(
+            "k create deploy web --image=nginx --replicas=3 -n prod",
+            "k create deploy web -n prod --replicas=3 --image=nginx"
+        ));
+        // Positional words keep their order.
+        assert!(!accepts("k label pod web app=api", "k label app=api pod web"));
+    }
+
+    #[test]
+    fn attached_and_separate_values_are_the_same() {
+        for answer in ["k get pod web -o yaml", "k get pod web -o=yaml", "k get pod web --output yaml"] {
+            assert!(accepts("k get pod web -oyaml", answer), "{answer}");
+        }
+        assert!(accepts("k run web --image=nginx", "k run web --image nginx"));
+        assert!(accepts("k get pods --namespace prod", "k get pods -nprod"));
+        assert!(!accepts("k get pod web -oyaml", "k get pod web -ojson"));
+    }
+
+    #[test]
+    fn bundled_switches_equal_separate_ones() {
+        let expected = "k run tmp --image=busybox -it --rm -- sh";
+        assert!(accepts(expected, "k run tmp --image=busybox -i -t --rm -- sh"));
+        assert!(accepts(expected, "k run tmp --rm --stdin --tty --image=busybox -- sh"));
+        assert!(!accepts(expected, "k run tmp --image=busybox -i --rm -- sh"));
+    }
+
+    #[test]
+    fn switches_do_not_take_the_next_word() {
+        let parsed = parse("k logs -f web");
+        assert_eq!(parsed[0].words, ["k", "logs", "web"]);
+        assert_eq!(parsed[0].flags, [flag("-f", None)]);
+
+        let parsed = parse("docker run -d -p 8080:80 nginx");
+        assert_eq!(parsed[0].words, ["docker", "run", "nginx"]);
+        assert_eq!(parsed[0].flags, [flag("-d", None), flag("-p", Some("8080:80"))]);
+    }
+
+    #[test]
+    fn values_follow_the_lint_tables() {
+        let parsed = parse("k create deploy --image nginx --dry-run web");
+        assert_eq!(parsed[0].words, ["k", "create", "deploy", "web"]);
+        assert_eq!(parsed[0].flags, [flag("--image", Some("nginx")), flag("--dry-run", None)]);
+
+        // `-p` is `--previous` for logs but a port for docker.
+        assert_eq!(parse("k logs -p web")[0].flags, [flag("-p", None)]);
+        assert_eq!(parse("podman run -p 80 web")[0].flags, [flag("-p", Some("80"))]);
+
+        // A flag the tables lack takes the next word.
+        assert_eq!(parse("k get pods --made-up web")[0].flags, [flag("--made-up", Some("web"))]);
+    }
+
+    #[test]
+    fn non_ascii_short_flags_are_split_on_characters() {
+        assert_eq!(parse("k get pods -é")[0].flags, [flag("-é", None)]);
+        assert_eq!(parse("k get pods -éyaml")[0].flags, [flag("-é", Some("yaml"))]);
+        assert_eq!(parse("k get pods -oé")[0].flags, [flag("-o", Some("é"))]);
+        assert!(!accepts("k get pods -oyaml", "k get pods -é"));
+    }
+
+    #[test]
+    fn quoting_and_comments_are_ignored() {
+        assert!(accepts("k exec web -- sh -c \"echo hi\"", "k exec web -- sh -c 'echo hi'"));
+        assert!(accepts("k get pods", "k get pods # list them"));
+        // Words after `--` are passed on verbatim, in order.
+        assert!(!accepts("k exec web -- ls -l /", "k exec web -- ls / -l"));
+    }
+
+    #[test]
+    fn placeholders_accept_any_value() {
+        let placeholders = ["POD_NAME", "NAMESPACE", "IMAGE"];
+        let check = |answer| check(answer, "k set image pod/POD_NAME app=IMAGE -n NAMESPACE", &[], &placeholders);
+        assert!(check("k set image pod/web app=nginx:1.25 -n prod").correct);
+        assert!(check("kubectl set image pod/api app=httpd --namespace=dev").correct);
+        // A placeholder stands for at least one character, and the literal
+        // text around it must match.
+        assert!(!check("k set image pod/ app=nginx -n prod").correct);
+        assert!(!check("k set image deploy/web app=nginx -n prod").correct);
+    }
+
+    #[test]
+    fn equivalents_are_accepted_too() {
+        let command = "k rollout undo deploy DEPLOY_NAME";
+        let equivalents = ["k rollout undo deploy/DEPLOY_NAME"];
+        let verdict = check("k rollout undo deploy/web", command, &equivalents, &["DEPLOY_NAME"]);
+        assert!(verdict.correct);
+        // The diff is still against the canonical command.
+        assert!(verdict.diff.iter().any(|token| token.change != Change::Same));
+        assert!(!check("k rollout undo web", command, &equivalents, &["DEPLOY_NAME"]).correct);
+    }
+
+    #[test]
+    fn diff_marks_missing_and_extra_words_and_flags() {
+        let tokens = |diff: Vec<DiffToken>| -> Vec<(String, Change)> {
+            diff.into_iter().map(|token| (token.text, token.change)).collect()
+        };
+        let expected = |items: &[(&str, Change)]| -> Vec<(String, Change)> {
+            items.iter().map(|&(text, change)| (text.to_string(), change)).collect()
+        };
+
+        assert_eq!(
+            tokens(diff("k get pods -n prod -oyaml", "kubectl get pod -n prod --watch", &[])),
+            expected(&[
+                ("k", Change::Same),
+                ("get", Change::Same),
+                ("pods", Change::Missing),
+                ("pod", Change::Extra),
+                ("-n prod", Change::Same),
+                ("-o yaml", Change::Missing),
+                ("--watch", Change::Extra),
+            ])
+        );
+        assert_eq!(
+            tokens(diff("k get pods | grep web", "k get pods", &[])),
+            expected(&[
+                ("k", Change::Same),
+                ("get", Change::Same),
+                ("pods", Change::Same),
+                ("|", Change::Missing),
+                ("grep", Change::Missing),
+                ("web", Change::Missing),
+            ])
+        );
+    }
+
+    #[test]
+    fn only_single_statements_are_drilled() {
+        assert!(is_single_line("k get pods | grep web"));
+        assert!(!is_single_line("k get pods; k get svc"));
+        assert!(!is_single_line("cat <<EOF > pod.yaml"));
+    }
+}
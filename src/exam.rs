@@ -0,0 +1,292 @@
+//! Mock exams: drawing tasks in proportion to the curriculum domains,
+//! scoring them per domain, and the locally stored results history.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::ckad_data::{Domain, ExamTask};
+use crate::rng::Rng;
+use crate::storage;
+
+const STORAGE_KEY: &str = "exam-history";
+
+/// Score needed to pass the real exam, in percent.
+pub const PASS_PERCENT: f64 = 66.0;
+
+/// Draws `count` tasks so that each domain gets a share close to its
+/// curriculum weight. Shares are rounded by largest remainder; a domain with
+/// too few tasks gives its unused share to the others. The tasks come back in
+/// random order, as in the exam.
+pub fn pick_tasks(tasks: &'static [ExamTask], count: usize, rng: &mut Rng) -> Vec<&'static ExamTask> {
+    let count = count.min(tasks.len());
+    let mut pools: BTreeMap<Domain, Vec<&'static ExamTask>> = BTreeMap::new();
+    for task in tasks {
+        pools.entry(task.domain).or_default().push(task);
+    }
+
+    let mut quotas: BTreeMap<Domain, usize> = BTreeMap::new();
+    let mut remainders = Vec::new();
+    for (&domain, pool) in &pools {
+        let exact = domain.weight() as usize * count;
+        let quota = (exact / 100).min(pool.len());
+        quotas.insert(domain, quota);
+        remainders.push((exact % 100, domain));
+    }
+    remainders.sort_by(|a, b| b.cmp(a));
+
+    // Hand out what rounding and small pools left over, largest remainder
+    // first, until every task slot is taken.
+    let mut left = count - quotas.values().sum::<usize>();
+    while left > 0 {
+        let before = left;
+        for &(_, domain) in &remainders {
+            if left > 0 && quotas[&domain] < pools[&domain].len() {
+                *quotas.get_mut(&domain).unwrap() += 1;
+                left -= 1;
+            }
+        }
+        if left == before {
+            break;
+        }
+    }
+
+    let mut picked = Vec::with_capacity(count);
+    for (domain, mut pool) in pools {
+        rng.shuffle(&mut pool);
+        picked.extend(pool.into_iter().take(quotas[&domain]));
+    }
+    rng.shuffle(&mut picked);
+    picked
+}
+
+/// Points earned out of points possible in one domain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Score {
+    pub earned: u32,
+    pub possible: u32,
+}
+
+impl Score {
+    pub fn percent(&self) -> f64 {
+        match self.possible {
+            0 => 0.0,
+            possible => f64::from(self.earned) * 100.0 / f64::from(possible),
+        }
+    }
+}
+
+/// Per-domain scores of the tasks, given which of them were solved.
+pub fn domain_scores(tasks: &[&ExamTask], solved: &[bool]) -> BTreeMap<Domain, Score> {
+    let mut scores: BTreeMap<Domain, Score> = BTreeMap::new();
+    for (task, &solved) in tasks.iter().zip(solved) {
+        let score = scores.entry(task.domain).or_insert(Score { earned: 0, possible: 0 });
+        score.possible += task.points;
+        if solved {
+            score.earned += task.points;
+        }
+    }
+    scores
+}
+
+/// One finished mock exam, as kept in the results history.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExamResult {
+    /// When the exam was submitted, in milliseconds since the Unix epoch.
+    pub finished_at: f64,
+    pub task_count: usize,
+    pub time_limit_minutes: u32,
+    pub seconds_used: u32,
+    pub total: Score,
+    /// Scores keyed by domain slug, so renamed domains do not break old
+    /// entries.
+    pub domains: BTreeMap<String, Score>,
+    /// Ids of the tasks that were not solved.
+    pub missed: Vec<String>,
+}
+
+impl ExamResult {
+    pub fn new(
+        tasks: &[&ExamTask],
+        solved: &[bool],
+        time_limit_minutes: u32,
+        seconds_used: u32,
+        finished_at: f64,
+    ) -> Self {
+        let scores = domain_scores(tasks, solved);
+        let total = Score {
+            earned: scores.values().map(|score| score.earned).sum(),
+            possible: scores.values().map(|score| score.possible).sum(),
+        };
+        ExamResult {
+            finished_at,
+            task_count: tasks.len(),
+            time_limit_minutes,
+            seconds_used,
+            total,
+            domains: scores.into_iter().map(|(domain, score)| (domain.slug().to_string(), score)).collect(),
+            missed: tasks
+                .iter()
+                .zip(solved)
+                .filter(|(_, &solved)| !solved)
+                .map(|(task, _)| task.id.to_string())
+                .collect(),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.total.percent() >= PASS_PERCENT
+    }
+}
+
+/// Past results, oldest first.
+pub fn load_history() -> Vec<ExamResult> {
+    storage::load(STORAGE_KEY).unwrap_or_default()
+}
+
+pub fn save_history(history: &[ExamResult]) {
+    storage::save(STORAGE_KEY, &history);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ckad_data::get_exam_tasks;
+
+    /// `per_domain` tasks of each domain, worth 1, 2, 3, ... points.
+    fn tasks(per_domain: impl Fn(Domain) -> usize) -> &'static [ExamTask] {
+        let tasks: Vec<ExamTask> = Domain::ALL
+            .into_iter()
+            .flat_map(|domain| (0..per_domain(domain)).map(move |index| (domain, index)))
+            .map(|(domain, index)| ExamTask {
+                id: Box::leak(format!("{}-{index}", domain.slug()).into_boxed_str()),
+                domain,
+                points: index as u32 + 1,
+                title: "",
+                task: "",
+                concepts: &[],
+            })
+            .collect();
+        Box::leak(tasks.into_boxed_slice())
+    }
+
+    fn shares(picked: &[&ExamTask]) -> BTreeMap<Domain, usize> {
+        let mut shares = BTreeMap::new();
+        for task in picked {
+            *shares.entry(task.domain).or_insert(0) += 1;
+        }
+        shares
+    }
+
+    fn assert_distinct(picked: &[&ExamTask]) {
+        let mut ids: Vec<&str> = picked.iter().map(|task| task.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), picked.len(), "a task was drawn twice");
+    }
+
+    #[test]
+    fn count_is_honored_without_repeats() {
+        let all = get_exam_tasks();
+        for count in [1, 5, 10, 16, all.len()] {
+            for seed in 0..20 {
+                let picked = pick_tasks(all, count, &mut Rng::new(seed));
+                assert_eq!(picked.len(), count);
+                assert_distinct(&picked);
+            }
+        }
+        assert_eq!(pick_tasks(all, all.len() + 10, &mut Rng::new(1)).len(), all.len());
+    }
+
+    #[test]
+    fn shares_follow_the_curriculum_weights() {
+        let all = tasks(|_| 10);
+        for seed in 0..20 {
+            let picked = pick_tasks(all, 20, &mut Rng::new(seed));
+            let expected = Domain::ALL.map(|domain| (domain, domain.weight() as usize / 5));
+            assert_eq!(shares(&picked), BTreeMap::from(expected));
+        }
+    }
+
+    #[test]
+    fn rounding_goes_to_the_largest_remainders() {
+        // 10 tasks: 2, 2, 1.5, 2.5 and 2. Observability and environment
+        // tie at .5; the tie goes to the later domain.
+        let picked = pick_tasks(tasks(|_| 10), 10, &mut Rng::new(7));
+        assert_eq!(
+            shares(&picked),
+            BTreeMap::from([
+                (Domain::DesignBuild, 2),
+                (Domain::Deployment, 2),
+                (Domain::Observability, 1),
+                (Domain::EnvironmentSecurity, 3),
+                (Domain::Networking, 2),
+            ])
+        );
+        // 7 tasks: 1.4, 1.4, 1.05, 1.75 and 1.4.
+        let picked = pick_tasks(tasks(|_| 10), 7, &mut Rng::new(7));
+        assert_eq!(
+            shares(&picked),
+            BTreeMap::from([
+                (Domain::DesignBuild, 1),
+                (Domain::Deployment, 1),
+                (Domain::Observability, 1),
+                (Domain::EnvironmentSecurity, 2),
+                (Domain::Networking, 2),
+            ])
+        );
+    }
+
+    #[test]
+    fn small_pools_give_their_share_to_the_others() {
+        let all = tasks(|domain| if domain == Domain::Networking { 1 } else { 10 });
+        let picked = pick_tasks(all, 20, &mut Rng::new(3));
+        assert_eq!(picked.len(), 20);
+        assert_distinct(&picked);
+        assert_eq!(shares(&picked)[&Domain::Networking], 1);
+    }
+
+    #[test]
+    fn scores_add_up_to_the_points_solved() {
+        let all = tasks(|_| 3);
+        for seed in 0..20 {
+            let mut rng = Rng::new(seed);
+            let picked = pick_tasks(all, 10, &mut rng);
+            let solved: Vec<bool> = picked.iter().map(|_| rng.below(2) == 1).collect();
+            let scores = domain_scores(&picked, &solved);
+
+            let points = |solved_only: bool| -> u32 {
+                picked
+                    .iter()
+                    .zip(&solved)
+                    .filter(|(_, &solved)| solved || !solved_only)
+                    .map(|(task, _)| task.points)
+                    .sum()
+            };
+            assert_eq!(scores.values().map(|score| score.earned).sum::<u32>(), points(true));
+            assert_eq!(scores.values().map(|score| score.possible).sum::<u32>(), points(false));
+            for (domain, score) in &scores {
+                let possible: u32 = picked.iter().filter(|task| task.domain == *domain).map(|task| task.points).sum();
+                assert_eq!(score.possible, possible);
+            }
+
+            let result = ExamResult::new(&picked, &solved, 120, 0, 0.0);
+            assert_eq!(result.total.earned, points(true));
+            assert_eq!(result.missed.len(), solved.iter().filter(|&&solved| !solved).count());
+        }
+    }
+
+    #[test]
+    fn pass_mark_is_inclusive() {
+        let result = |earned| ExamResult {
+            finished_at: 0.0,
+            task_count: 1,
+            time_limit_minutes: 120,
+            seconds_used: 0,
+            total: Score { earned, possible: 50 },
+            domains: BTreeMap::new(),
+            missed: Vec::new(),
+        };
+        assert!(result(33).passed());
+        assert!(!result(32).passed());
+        assert_eq!(Score { earned: 0, possible: 0 }.percent(), 0.0);
+    }
+}
@@ -0,0 +1,40 @@
+use leptos::*;
+use crate::storage;
+
+const STORAGE_KEY: &str = "favorites";
+
+/// Ids of the starred concepts, persisted in `localStorage`. Ids rather than
+/// positions are stored, so favorites survive catalog reordering; ids of
+/// concepts that were since removed are kept but never shown.
+#[derive(Clone, Copy)]
+pub struct Favorites(RwSignal<Vec<String>>);
+
+impl Favorites {
+    /// Loads the favorites and makes them available to child components.
+    pub fn provide() -> Favorites {
+        let ids: Vec<String> = storage::load(STORAGE_KEY).unwrap_or_default();
+        let favorites = Favorites(create_rw_signal(ids));
+        provide_context(favorites);
+        favorites
+    }
+
+    pub fn expect() -> Favorites {
+        expect_context::<Favorites>()
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.0.with(|ids| ids.iter().any(|favorite| favorite == id))
+    }
+
+    pub fn toggle(&self, id: &str) {
+        self.0.update(|ids| {
+            match ids.iter().position(|favorite| favorite == id) {
+                Some(index) => {
+                    ids.remove(index);
+                }
+                None => ids.push(id.to_string()),
+            }
+            storage::save(STORAGE_KEY, ids);
+        });
+    }
+}
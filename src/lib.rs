@@ -1,8 +1,32 @@
 use leptos::*;
 use wasm_bindgen::prelude::*;
 
+mod catalog_rules;
+mod custom;
+mod drill;
+mod clipboard;
 mod components;
-mod ckad_data;
+mod domains;
+mod exam;
+mod favorites;
+mod files;
+pub mod ckad_data;
+pub mod lint;
+pub mod manifest;
+mod notes;
+mod placeholders;
+mod pod_builder;
+mod progress;
+mod quiz;
+mod rng;
+mod routing;
+pub mod search;
+mod session;
+mod shell;
+mod spelling;
+mod srs;
+mod storage;
+mod yaml;
 
 use components::CkadWiki;
 
@@ -0,0 +1,528 @@
+//! The subcommands and flags the linter knows, per tool. The tables cover
+//! what the CKAD and its usual tooling need, not every flag a release ships;
+//! add a flag here when a command in the catalog needs it.
+//!
+//! A flag written `--name=` takes a value, as in `--image=nginx` or
+//! `--image nginx`. One written without the `=` is a switch: it does not
+//! take the next word, although `--flag=value` is still accepted, as for
+//! `--dry-run=client` or `--all=false`.
+
+/// A flag group shared by several subcommands.
+pub type Flags = &'static [&'static str];
+
+pub struct Program {
+    pub name: &'static str,
+    /// Flags every subcommand accepts.
+    pub global: Flags,
+    /// Subcommands by their path, e.g. `create secret generic`. A path that
+    /// only prefixes others, such as `rollout`, needs one of them.
+    pub commands: &'static [(&'static str, &'static [Flags])],
+    /// Other names of subcommands: `(parent path, alias, name)`.
+    pub aliases: &'static [(&'static str, &'static str, &'static str)],
+}
+
+impl Program {
+    pub fn command(&self, path: &str) -> Option<&'static [Flags]> {
+        self.commands.iter().find(|(name, _)| *name == path).map(|(_, flags)| *flags)
+    }
+
+    /// Whether `path` is a subcommand or the start of one.
+    pub fn has_path(&self, path: &str) -> bool {
+        self.commands.iter().any(|(name, _)| {
+            name.strip_prefix(path).is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+        })
+    }
+
+    /// The subcommands directly below `path`, e.g. `undo` for `rollout`.
+    pub fn children(&self, path: &str) -> Vec<&'static str> {
+        let prefix = if path.is_empty() { String::new() } else { format!("{path} ") };
+        let mut children: Vec<&str> = self
+            .commands
+            .iter()
+            .filter_map(|(name, _)| name.strip_prefix(prefix.as_str()))
+            .map(|rest| rest.split(' ').next().unwrap_or(rest))
+            .filter(|child| !child.is_empty())
+            .collect();
+        children.dedup();
+        children
+    }
+
+    /// The flags `path` accepts, including the global ones.
+    pub fn flags(&self, path: &str) -> impl Iterator<Item = &'static str> {
+        let groups: &[Flags] = self.command(path).unwrap_or_default();
+        groups.iter().copied().chain([self.global]).flat_map(|group| group.iter().copied())
+    }
+
+    /// Whether the flag takes a value in `path`, or `None` if it is unknown
+    /// there.
+    pub fn takes_value(&self, path: &str, name: &str) -> Option<bool> {
+        self.flags(path).find_map(|flag| match flag.strip_suffix('=') {
+            Some(option) => (option == name).then_some(true),
+            None => (flag == name).then_some(false),
+        })
+    }
+
+    /// The name of the subcommand `word` stands for below `parent`.
+    pub fn resolve<'a>(&self, parent: &str, word: &'a str) -> &'a str {
+        self.aliases
+            .iter()
+            .find(|(alias_parent, alias, _)| *alias_parent == parent && *alias == word)
+            .map_or(word, |(_, _, name)| *name)
+    }
+}
+
+pub fn program(family: &str) -> Option<&'static Program> {
+    [&KUBECTL, &HELM, &DOCKER].into_iter().find(|program| program.name == family)
+}
+
+const K_GLOBAL: Flags = &[
+    "-n=", "--namespace=", "--context=", "--kubeconfig=", "--cluster=", "--user=", "-s=", "--server=",
+    "--request-timeout=", "-v=", "--v=", "--as=", "--as-group=", "--token=", "--certificate-authority=",
+    "--client-certificate=", "--client-key=", "--cache-dir=", "--tls-server-name=", "-h", "--help",
+    "--insecure-skip-tls-verify", "--match-server-version", "--warnings-as-errors",
+];
+
+const K_OUTPUT: Flags = &[
+    "-o=", "--output=", "--template=", "--allow-missing-template-keys", "--show-managed-fields",
+];
+
+/// Choosing objects from files or by label.
+const K_SELECT: Flags = &["-f=", "--filename=", "-k=", "--kustomize=", "-l=", "--selector=", "-R", "--recursive"];
+
+/// Flags of commands that write objects.
+const K_WRITE: Flags = &["--dry-run", "--field-manager=", "--validate", "--save-config"];
+
+const K_DELETE: Flags = &[
+    "--all", "-A", "--all-namespaces", "--field-selector=", "--force", "--now", "--grace-period=",
+    "--timeout=", "--cascade", "--wait", "--ignore-not-found", "--dry-run", "-i", "--interactive", "--raw=",
+];
+
+const K_SECRET_DATA: Flags = &["--from-literal=", "--from-file=", "--from-env-file=", "--append-hash"];
+
+const K_SUBJECTS: Flags = &["--serviceaccount=", "--user=", "--group="];
+
+const K_ATTACH: Flags = &[
+    "-c=", "--container=", "-i", "--stdin", "-t", "--tty", "-q", "--quiet", "--pod-running-timeout=",
+];
+
+pub const KUBECTL: Program = Program {
+    name: "kubectl",
+    global: K_GLOBAL,
+    commands: &[
+        (
+            "get",
+            &[K_OUTPUT, K_SELECT, &[
+                "-A", "--all-namespaces", "--field-selector=", "-w", "--watch", "--watch-only", "--output-watch-events",
+                "--show-labels", "--show-kind", "-L=", "--label-columns=", "--sort-by=", "--no-headers",
+                "--ignore-not-found", "--chunk-size=", "--raw=", "--subresource=", "--server-print",
+            ]],
+        ),
+        ("describe", &[K_SELECT, &["-A", "--all-namespaces", "--show-events", "--chunk-size="]]),
+        ("explain", &[&["--recursive", "--api-version=", "-o=", "--output="]]),
+        (
+            "create",
+            &[K_OUTPUT, K_WRITE, &[
+                "-f=", "--filename=", "-k=", "--kustomize=", "-R", "--recursive", "--edit", "--raw=",
+                "--windows-line-endings",
+            ]],
+        ),
+        (
+            "create clusterrole",
+            &[K_OUTPUT, K_WRITE, &[
+                "--verb=", "--resource=", "--resource-name=", "--non-resource-url=", "--aggregation-rule=",
+            ]],
+        ),
+        ("create clusterrolebinding", &[K_OUTPUT, K_WRITE, K_SUBJECTS, &["--clusterrole="]]),
+        ("create configmap", &[K_OUTPUT, K_WRITE, K_SECRET_DATA]),
+        ("create cronjob", &[K_OUTPUT, K_WRITE, &["--image=", "--schedule=", "--restart="]]),
+        ("create deployment", &[K_OUTPUT, K_WRITE, &["--image=", "--replicas=", "-r=", "--port="]]),
+        ("create ingress", &[K_OUTPUT, K_WRITE, &["--rule=", "--class=", "--default-backend=", "--annotation="]]),
+        ("create job", &[K_OUTPUT, K_WRITE, &["--image=", "--from="]]),
+        ("create namespace", &[K_OUTPUT, K_WRITE]),
+        (
+            "create poddisruptionbudget",
+            &[K_OUTPUT, K_WRITE, &[
+                "--selector=", "--min-available=", "--max-unavailable=",
+            ]],
+        ),
+        (
+            "create priorityclass",
+            &[K_OUTPUT, K_WRITE, &[
+                "--value=", "--description=", "--preemption-policy=", "--global-default",
+            ]],
+        ),
+        ("create quota", &[K_OUTPUT, K_WRITE, &["--hard=", "--scopes="]]),
+        ("create role", &[K_OUTPUT, K_WRITE, &["--verb=", "--resource=", "--resource-name="]]),
+        ("create rolebinding", &[K_OUTPUT, K_WRITE, K_SUBJECTS, &["--role=", "--clusterrole="]]),
+        (
+            "create secret docker-registry",
+            &[K_OUTPUT, K_WRITE, &[
+                "--docker-server=", "--docker-username=", "--docker-password=", "--docker-email=", "--from-file=",
+                "--append-hash",
+            ]],
+        ),
+        ("create secret generic", &[K_OUTPUT, K_WRITE, K_SECRET_DATA, &["--type="]]),
+        ("create secret tls", &[K_OUTPUT, K_WRITE, &["--cert=", "--key=", "--append-hash"]]),
+        ("create service clusterip", &[K_OUTPUT, K_WRITE, &["--tcp=", "--clusterip="]]),
+        ("create service externalname", &[K_OUTPUT, K_WRITE, &["--tcp=", "--external-name="]]),
+        ("create service loadbalancer", &[K_OUTPUT, K_WRITE, &["--tcp="]]),
+        ("create service nodeport", &[K_OUTPUT, K_WRITE, &["--tcp=", "--node-port="]]),
+        ("create serviceaccount", &[K_OUTPUT, K_WRITE]),
+        (
+            "create token",
+            &[K_OUTPUT, &[
+                "--audience=", "--duration=", "--bound-object-kind=", "--bound-object-name=", "--bound-object-uid=",
+            ]],
+        ),
+        (
+            "run",
+            &[K_OUTPUT, K_WRITE, K_ATTACH, &[
+                "--image=", "--env=", "--labels=", "-l=", "--port=", "--restart=", "--overrides=", "--annotations=",
+                "--image-pull-policy=", "--rm", "--attach", "--command", "--expose", "--privileged",
+                "--leave-stdin-open",
+            ]],
+        ),
+        (
+            "expose",
+            &[K_OUTPUT, K_WRITE, K_SELECT, &[
+                "--name=", "--port=", "--target-port=", "--type=", "--protocol=", "--labels=", "--external-ip=",
+                "--cluster-ip=", "--load-balancer-ip=", "--session-affinity=", "--overrides=", "--override-type=",
+            ]],
+        ),
+        (
+            "apply",
+            &[K_OUTPUT, K_WRITE, K_SELECT, &[
+                "--server-side", "--force", "--force-conflicts", "--prune", "--prune-allowlist=", "--all",
+                "--overwrite",
+                "--grace-period=", "--timeout=", "--cascade", "--wait",
+            ]],
+        ),
+        ("delete", &[K_SELECT, K_DELETE, &["-o=", "--output="]]),
+        ("edit", &[K_OUTPUT, K_WRITE, K_SELECT, &["--subresource=", "--output-patch", "--windows-line-endings"]]),
+        (
+            "patch",
+            &[K_OUTPUT, K_WRITE, K_SELECT, &[
+                "-p=", "--patch=", "--patch-file=", "--type=", "--local", "--subresource=",
+            ]],
+        ),
+        ("replace", &[K_OUTPUT, K_WRITE, K_SELECT, K_DELETE, &["--subresource="]]),
+        (
+            "label",
+            &[K_OUTPUT, K_WRITE, K_SELECT, &[
+                "--all", "-A", "--all-namespaces", "--field-selector=", "--overwrite", "--list", "--local",
+                "--resource-version=",
+            ]],
+        ),
+        (
+            "annotate",
+            &[K_OUTPUT, K_WRITE, K_SELECT, &[
+                "--all", "-A", "--all-namespaces", "--field-selector=", "--overwrite", "--list", "--local",
+                "--resource-version=",
+            ]],
+        ),
+        (
+            "set env",
+            &[K_OUTPUT, K_WRITE, K_SELECT, &[
+                "-e=", "--env=", "-c=", "--containers=", "--from=", "--prefix=", "--keys=", "--all", "--list",
+                "--local", "--overwrite", "--resolve",
+            ]],
+        ),
+        ("set image", &[K_OUTPUT, K_WRITE, K_SELECT, &["--all", "--local"]]),
+        (
+            "set resources",
+            &[K_OUTPUT, K_WRITE, K_SELECT, &[
+                "-c=", "--containers=", "--limits=", "--requests=", "--all", "--local",
+            ]],
+        ),
+        ("set selector", &[K_OUTPUT, K_WRITE, K_SELECT, &["--all", "--local", "--resource-version="]]),
+        ("set serviceaccount", &[K_OUTPUT, K_WRITE, K_SELECT, &["--all", "--local"]]),
+        (
+            "scale",
+            &[K_OUTPUT, K_SELECT, &[
+                "--replicas=", "--current-replicas=", "--resource-version=", "--all", "--timeout=", "--dry-run",
+            ]],
+        ),
+        ("autoscale", &[K_OUTPUT, K_WRITE, K_SELECT, &["--min=", "--max=", "--cpu-percent=", "--name="]]),
+        ("rollout history", &[K_OUTPUT, K_SELECT, &["--revision="]]),
+        ("rollout pause", &[K_OUTPUT, K_SELECT, &["--field-manager="]]),
+        ("rollout restart", &[K_OUTPUT, K_SELECT, &["--field-manager="]]),
+        ("rollout resume", &[K_OUTPUT, K_SELECT, &["--field-manager="]]),
+        ("rollout status", &[K_SELECT, &["--revision=", "--timeout=", "-w", "--watch"]]),
+        ("rollout undo", &[K_OUTPUT, K_SELECT, &["--to-revision=", "--dry-run"]]),
+        (
+            "logs",
+            &[&[
+                "-c=", "--container=", "-l=", "--selector=", "--since=", "--since-time=", "--tail=", "--limit-bytes=",
+                "--max-log-requests=", "--pod-running-timeout=", "-f", "--follow", "-p", "--previous", "--timestamps",
+                "--all-containers", "--all-pods", "--prefix", "--ignore-errors",
+            ]],
+        ),
+        ("exec", &[K_ATTACH, &["-f=", "--filename="]]),
+        ("attach", &[K_ATTACH]),
+        ("cp", &[&["-c=", "--container=", "--retries=", "--no-preserve"]]),
+        ("port-forward", &[&["--address=", "--pod-running-timeout="]]),
+        (
+            "debug",
+            &[K_ATTACH, &[
+                "--image=", "--target=", "--copy-to=", "--profile=", "--env=", "--set-image=", "--image-pull-policy=",
+                "--custom=", "--attach", "--replace", "--same-node", "--share-processes",
+            ]],
+        ),
+        (
+            "top pod",
+            &[&[
+                "-l=", "--selector=", "--field-selector=", "--sort-by=", "-A", "--all-namespaces", "--containers",
+                "--no-headers", "--sum",
+            ]],
+        ),
+        ("top node", &[&["-l=", "--selector=", "--sort-by=", "--no-headers", "--show-capacity"]]),
+        ("config current-context", &[]),
+        ("config delete-context", &[]),
+        ("config get-contexts", &[&["-o=", "--output=", "--no-headers"]]),
+        ("config rename-context", &[]),
+        ("config set", &[]),
+        ("config set-cluster", &[&["--server=", "--certificate-authority=", "--embed-certs"]]),
+        ("config set-context", &[&["--current", "--cluster=", "--user=", "--namespace="]]),
+        (
+            "config set-credentials",
+            &[&[
+                "--token=", "--username=", "--password=", "--client-certificate=", "--client-key=", "--embed-certs",
+            ]],
+        ),
+        ("config unset", &[]),
+        ("config use-context", &[]),
+        ("config view", &[K_OUTPUT, &["--minify", "--raw", "--flatten"]]),
+        ("auth can-i", &[&["-A", "--all-namespaces", "--list", "--no-headers", "-q", "--quiet", "--subresource="]]),
+        ("auth whoami", &[&["-o=", "--output="]]),
+        (
+            "api-resources",
+            &[&[
+                "-o=", "--output=", "--api-group=", "--sort-by=", "--categories=", "--verbs=", "--namespaced",
+                "--no-headers", "--cached",
+            ]],
+        ),
+        ("api-versions", &[]),
+        ("cluster-info", &[]),
+        (
+            "cluster-info dump",
+            &[&[
+                "-o=", "--output=", "--output-directory=", "-A", "--all-namespaces", "--namespaces=",
+            ]],
+        ),
+        ("version", &[&["-o=", "--output=", "--client"]]),
+        (
+            "wait",
+            &[K_SELECT, &[
+                "--for=", "--timeout=", "--field-selector=", "--all", "-A", "--all-namespaces", "--local",
+            ]],
+        ),
+        (
+            "events",
+            &[&[
+                "-o=", "--output=", "--for=", "--types=", "-A", "--all-namespaces", "-w", "--watch", "--no-headers",
+            ]],
+        ),
+        (
+            "taint",
+            &[K_OUTPUT, &[
+                "-l=", "--selector=", "--field-manager=", "--all", "--overwrite", "--dry-run", "--validate",
+            ]],
+        ),
+        ("cordon", &[&["-l=", "--selector=", "--dry-run"]]),
+        ("uncordon", &[&["-l=", "--selector=", "--dry-run"]]),
+        (
+            "drain",
+            &[&[
+                "-l=", "--selector=", "--pod-selector=", "--grace-period=", "--timeout=",
+                "--skip-wait-for-delete-timeout=",
+                "--force", "--ignore-daemonsets", "--delete-emptydir-data", "--disable-eviction", "--dry-run",
+            ]],
+        ),
+        ("diff", &[K_SELECT, &["--field-manager=", "--server-side", "--force-conflicts", "--prune"]]),
+        // Provided by the kubectl-convert plugin.
+        ("convert", &[K_OUTPUT, K_SELECT, &["--output-version=", "--local", "--validate"]]),
+        ("completion", &[]),
+        ("kustomize", &[]),
+        ("proxy", &[&["-p=", "--port=", "--address=", "--accept-hosts="]]),
+    ],
+    aliases: &[
+        ("create", "cj", "cronjob"),
+        ("create", "cm", "configmap"),
+        ("create", "deploy", "deployment"),
+        ("create", "ns", "namespace"),
+        ("create", "pc", "priorityclass"),
+        ("create", "pdb", "poddisruptionbudget"),
+        ("create", "resourcequota", "quota"),
+        ("create", "sa", "serviceaccount"),
+        ("create", "svc", "service"),
+        ("top", "po", "pod"),
+        ("top", "pods", "pod"),
+        ("top", "no", "node"),
+        ("top", "nodes", "node"),
+    ],
+};
+
+const H_GLOBAL: Flags = &[
+    "-n=", "--namespace=", "--kube-context=", "--kubeconfig=", "--repository-config=", "--registry-config=",
+    "--debug", "-h", "--help",
+];
+
+/// Flags of commands that render a chart.
+const H_CHART: Flags = &[
+    "--set=", "--set-string=", "--set-file=", "--set-json=", "-f=", "--values=", "--version=", "--repo=",
+    "--devel", "--dry-run", "--wait", "--wait-for-jobs", "--timeout=", "--atomic", "--no-hooks", "--skip-crds",
+    "--description=", "--post-renderer=", "--dependency-update", "--disable-openapi-validation", "-o=",
+    "--output=", "--create-namespace", "--username=", "--password=", "--verify",
+];
+
+pub const HELM: Program = Program {
+    name: "helm",
+    global: H_GLOBAL,
+    commands: &[
+        ("install", &[H_CHART, &["-g", "--generate-name", "--replace", "--name-template="]]),
+        (
+            "upgrade",
+            &[H_CHART, &[
+                "-i", "--install", "--reuse-values", "--reset-values", "--reset-then-reuse-values", "--force",
+                "--history-max=", "--cleanup-on-fail",
+            ]],
+        ),
+        (
+            "template",
+            &[H_CHART, &[
+                "--output-dir=", "-s=", "--show-only=", "--include-crds", "-a=", "--api-versions=", "--kube-version=",
+                "--is-upgrade", "--validate", "--release-name", "-g", "--generate-name",
+            ]],
+        ),
+        (
+            "uninstall",
+            &[&[
+                "--keep-history", "--dry-run", "--wait", "--timeout=", "--no-hooks", "--cascade=", "--description=",
+            ]],
+        ),
+        (
+            "list",
+            &[&[
+                "-a", "--all", "-A", "--all-namespaces", "-q", "--short", "-o=", "--output=", "--deployed", "--failed",
+                "--pending", "--uninstalled", "--uninstalling", "--superseded", "-f=", "--filter=", "-l=",
+                "--selector=",
+                "-m=", "--max=", "--offset=", "-r", "--reverse", "-d", "--date", "--no-headers", "--time-format=",
+            ]],
+        ),
+        ("status", &[&["-o=", "--output=", "--revision=", "--show-desc", "--show-resources"]]),
+        ("history", &[&["-o=", "--output=", "--max="]]),
+        (
+            "rollback",
+            &[&[
+                "--wait", "--timeout=", "--force", "--dry-run", "--cleanup-on-fail", "--no-hooks", "--recreate-pods",
+                "--history-max=",
+            ]],
+        ),
+        ("get all", &[&["--revision=", "--template="]]),
+        ("get hooks", &[&["--revision="]]),
+        ("get manifest", &[&["--revision="]]),
+        ("get notes", &[&["--revision="]]),
+        ("get values", &[&["-a", "--all", "-o=", "--output=", "--revision="]]),
+        ("show all", &[&["--version=", "--repo=", "--devel"]]),
+        ("show chart", &[&["--version=", "--repo=", "--devel"]]),
+        ("show crds", &[&["--version=", "--repo=", "--devel"]]),
+        ("show readme", &[&["--version=", "--repo=", "--devel"]]),
+        ("show values", &[&["--version=", "--repo=", "--devel", "--jsonpath="]]),
+        ("repo add", &[&["--username=", "--password=", "--force-update", "--pass-credentials"]]),
+        ("repo list", &[&["-o=", "--output="]]),
+        ("repo remove", &[]),
+        ("repo update", &[&["--fail-on-repo-update-fail"]]),
+        ("search hub", &[&["-o=", "--output=", "--endpoint=", "--max-col-width=", "--list-repo-url"]]),
+        (
+            "search repo",
+            &[&[
+                "--versions", "-l", "--version=", "--devel", "-r", "--regexp", "-o=", "--output=", "--max-col-width=",
+                "--fail-on-no-result",
+            ]],
+        ),
+        (
+            "pull",
+            &[&[
+                "--untar", "--untardir=", "-d=", "--destination=", "--version=", "--repo=", "--devel", "--prov",
+                "--verify",
+            ]],
+        ),
+        ("create", &[&["-p=", "--starter="]]),
+        ("package", &[&["-d=", "--destination=", "--version=", "--app-version=", "-u", "--dependency-update"]]),
+        ("lint", &[&["--strict", "-f=", "--values=", "--set=", "--set-string=", "--with-subcharts", "--quiet"]]),
+        ("dependency build", &[&["--skip-refresh", "--verify"]]),
+        ("dependency list", &[]),
+        ("dependency update", &[&["--skip-refresh", "--verify"]]),
+        ("env", &[]),
+        ("version", &[&["--short", "--template="]]),
+    ],
+    aliases: &[
+        ("", "del", "uninstall"),
+        ("", "delete", "uninstall"),
+        ("", "un", "uninstall"),
+        ("", "ls", "list"),
+        ("", "hist", "history"),
+        ("", "inspect", "show"),
+        ("", "fetch", "pull"),
+        ("", "dep", "dependency"),
+        ("repo", "ls", "list"),
+        ("repo", "rm", "remove"),
+    ],
+};
+
+const D_GLOBAL: Flags = &[
+    "-H=", "--host=", "--context=", "--config=", "-l=", "--log-level=", "-D", "--debug", "--help",
+];
+
+const D_CONTAINER: Flags = &[
+    "-d", "--detach", "-i", "--interactive", "-t", "--tty", "-e=", "--env=", "--env-file=", "-u=", "--user=",
+    "-w=", "--workdir=", "--privileged",
+];
+
+pub const DOCKER: Program = Program {
+    name: "docker",
+    global: D_GLOBAL,
+    commands: &[
+        (
+            "build",
+            &[&[
+                "-t=", "--tag=", "-f=", "--file=", "--build-arg=", "--target=", "--platform=", "--label=", "--network=",
+                "--progress=", "--secret=", "--ssh=", "--iidfile=", "--format=", "--no-cache", "--pull", "-q",
+                "--quiet",
+                "--rm", "--layers",
+            ]],
+        ),
+        ("push", &[&["-a", "--all-tags", "-q", "--quiet", "--tls-verify", "--creds="]]),
+        ("pull", &[&["-a", "--all-tags", "-q", "--quiet", "--platform=", "--tls-verify", "--creds="]]),
+        ("tag", &[]),
+        ("save", &[&["-o=", "--output=", "--format="]]),
+        ("load", &[&["-i=", "--input=", "-q", "--quiet"]]),
+        (
+            "run",
+            &[D_CONTAINER, &[
+                "--name=", "--rm", "-p=", "--publish=", "-P", "--publish-all", "-v=", "--volume=", "--mount=",
+                "--entrypoint=", "--network=", "--restart=", "--init", "--label=", "-m=", "--memory=", "--cpus=",
+                "--platform=", "--pull=", "--hostname=",
+            ]],
+        ),
+        ("exec", &[D_CONTAINER]),
+        ("logs", &[&["-f", "--follow", "-t", "--timestamps", "--tail=", "-n=", "--since=", "--until=", "--details"]]),
+        (
+            "ps",
+            &[&[
+                "-a", "--all", "-q", "--quiet", "-f=", "--filter=", "--format=", "-n=", "--last=", "-l", "--latest",
+                "--no-trunc", "-s", "--size",
+            ]],
+        ),
+        ("images", &[&["-a", "--all", "-q", "--quiet", "-f=", "--filter=", "--format=", "--digests", "--no-trunc"]]),
+        ("inspect", &[&["-f=", "--format=", "-s", "--size", "--type="]]),
+        ("rm", &[&["-f", "--force", "-v", "--volumes"]]),
+        ("rmi", &[&["-f", "--force", "--no-prune"]]),
+        ("start", &[&["-a", "--attach", "-i", "--interactive"]]),
+        ("stop", &[&["-t=", "--time="]]),
+        ("restart", &[&["-t=", "--time="]]),
+        ("login", &[&["-u=", "--username=", "-p=", "--password=", "--password-stdin"]]),
+        ("logout", &[]),
+    ],
+    aliases: &[],
+};
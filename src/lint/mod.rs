@@ -0,0 +1,358 @@
+//! A linter for shell commands. Invocations of kubectl (or `k`), helm,
+//! docker and podman are checked against the subcommands and flags in
+//! `flags`; other programs, such as `grep` in a pipeline, are left alone.
+//!
+//! Besides unknown subcommands and flags, it reports flags that lack their
+//! value and forms that are deprecated or were removed, such as a bare
+//! `--dry-run` or `kubectl run --replicas`.
+
+mod flags;
+
+use crate::shell::{program_family, split_short_flag, tokenize, Word};
+use crate::spelling::closest;
+use flags::Program;
+
+/// Formats kubectl's `--output` accepts, before any `=` argument as in
+/// `jsonpath={.metadata.name}`.
+const OUTPUT_FORMATS: &[&str] = &[
+    "json",
+    "yaml",
+    "wide",
+    "name",
+    "jsonpath",
+    "jsonpath-file",
+    "jsonpath-as-json",
+    "custom-columns",
+    "custom-columns-file",
+    "go-template",
+    "go-template-file",
+    "template",
+    "templatefile",
+    "plaintext",
+    "plaintext-openapiv2",
+];
+
+/// Something wrong with a command, at a 1-based line of it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    pub line: usize,
+    pub message: String,
+}
+
+impl Problem {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Problem { line, message: message.into() }
+    }
+}
+
+/// ` (did you mean `x`?)` when `word` looks like a misspelling of a candidate.
+fn suggestion<'a>(word: &str, candidates: impl IntoIterator<Item = &'a str>) -> String {
+    closest(word, candidates).map(|name| format!(" (did you mean `{name}`?)")).unwrap_or_default()
+}
+
+/// Forms that work, or did until recently, but should not be learned.
+fn deprecation(program: &str, path: &str, flag: &str, value: Option<&str>) -> Option<&'static str> {
+    match (program, path, flag, value) {
+        ("kubectl", _, "--dry-run", None | Some("true" | "false")) => {
+            Some("`--dry-run` without a strategy is deprecated; use `--dry-run=client` or `--dry-run=server`")
+        }
+        ("kubectl", _, "--record", _) => {
+            Some("`--record` is deprecated; set the `kubernetes.io/change-cause` annotation instead")
+        }
+        ("kubectl", "run", "--generator" | "--replicas" | "--schedule", _) => {
+            Some("`kubectl run` only creates Pods since 1.18; use `kubectl create deployment` or `create cronjob`")
+        }
+        ("kubectl", "get", "--export", _) => {
+            Some("`--export` was removed in kubectl 1.18; strip the status and server-set fields by hand")
+        }
+        ("helm", "install" | "template", "--name", _) => {
+            Some("`--name` is from Helm 2; Helm 3 takes the release name as the first argument")
+        }
+        ("helm", "uninstall", "--purge", _) => Some("`--purge` is from Helm 2; Helm 3 always removes the release"),
+        _ => None,
+    }
+}
+
+/// Checks the value of a flag the program knows, if it has a fixed set.
+fn value_problem(program: &str, flag: &str, value: &str) -> Option<String> {
+    match (program, flag) {
+        ("kubectl", "-o" | "--output") => {
+            let format = value.split_once('=').map_or(value, |(format, _)| format);
+            (!OUTPUT_FORMATS.contains(&format)).then(|| {
+                format!("unknown output format `{format}`{}", suggestion(format, OUTPUT_FORMATS.iter().copied()))
+            })
+        }
+        ("kubectl", "--dry-run") => (!matches!(value, "client" | "server" | "none"))
+            .then(|| format!("`--dry-run` must be `client`, `server` or `none`, not `{value}`")),
+        _ => None,
+    }
+}
+
+/// One invocation being checked, with the subcommand found so far.
+struct Checker<'a> {
+    program: &'static Program,
+    path: String,
+    problems: &'a mut Vec<Problem>,
+}
+
+impl Checker<'_> {
+    /// `kubectl create deployment`, as it is called in messages.
+    fn command_name(&self) -> String {
+        if self.path.is_empty() {
+            self.program.name.to_string()
+        } else {
+            format!("{} {}", self.program.name, self.path)
+        }
+    }
+
+    fn flags(&self) -> impl Iterator<Item = &'static str> {
+        self.program.flags(&self.path)
+    }
+
+    /// Whether the flag takes a value, or `None` if it is unknown.
+    fn takes_value(&self, name: &str) -> Option<bool> {
+        self.program.takes_value(&self.path, name)
+    }
+
+    fn report_unknown(&mut self, line: usize, name: &str) {
+        let names: Vec<&str> = self.flags().map(|flag| flag.trim_end_matches('=')).collect();
+        let message = format!("unknown flag `{name}` for `{}`{}", self.command_name(), suggestion(name, names));
+        self.problems.push(Problem::new(line, message));
+    }
+
+    /// Checks a flag, given with its value or followed by the `rest` of the
+    /// invocation, from which it takes its value if it needs one.
+    fn check_flag<'w>(
+        &mut self,
+        line: usize,
+        name: &str,
+        value: Option<&'w str>,
+        rest: &mut impl Iterator<Item = &'w (String, usize)>,
+    ) {
+        if let Some(message) = deprecation(self.program.name, &self.path, name, value) {
+            self.problems.push(Problem::new(line, message));
+            return;
+        }
+        let value = match (self.takes_value(name), value) {
+            (None, _) => return self.report_unknown(line, name),
+            (Some(true), None) => match rest.next() {
+                Some((next, _)) if !next.starts_with('-') || next == "-" => Some(next.as_str()),
+                _ => {
+                    self.problems.push(Problem::new(line, format!("`{name}` needs a value")));
+                    return;
+                }
+            },
+            (_, value) => value,
+        };
+        if let Some(message) = value.and_then(|value| value_problem(self.program.name, name, value)) {
+            self.problems.push(Problem::new(line, message));
+        }
+    }
+
+    /// Takes a positional word while it still names a deeper subcommand.
+    /// Returns `false` if the word should have been a subcommand but is not.
+    fn enter(&mut self, line: usize, word: &str) -> bool {
+        let name = self.program.resolve(&self.path, word);
+        let candidate = if self.path.is_empty() { name.to_string() } else { format!("{} {name}", self.path) };
+        if self.program.has_path(&candidate) {
+            self.path = candidate;
+            return true;
+        }
+        if self.program.command(&self.path).is_none() {
+            let children = self.program.children(&self.path);
+            let message = format!(
+                "unknown subcommand `{word}` for `{}`{}",
+                self.command_name(),
+                suggestion(word, children.iter().copied()),
+            );
+            self.problems.push(Problem::new(line, message));
+            return false;
+        }
+        true
+    }
+}
+
+fn lint_invocation(words: &[(String, usize)], problems: &mut Vec<Problem>) {
+    // `sudo` does not change what the command means.
+    let mut words = words.iter().skip_while(|(word, _)| word == "sudo");
+    let Some(program) = words
+        .next()
+        .and_then(|(word, _)| program_family(word))
+        .and_then(flags::program)
+    else {
+        return;
+    };
+    let mut checker = Checker { program, path: String::new(), problems };
+    // Whether positional words may still name a deeper subcommand.
+    let mut in_path = true;
+    let mut last_line = 1;
+
+    while let Some((word, line)) = words.next() {
+        let line = *line;
+        last_line = line;
+        if word == "--" {
+            // The rest is passed on to another program.
+            break;
+        }
+        if let Some(long) = word.strip_prefix("--").filter(|long| !long.is_empty()) {
+            match long.split_once('=') {
+                Some((name, value)) => checker.check_flag(line, &format!("--{name}"), Some(value), &mut words),
+                None => checker.check_flag(line, word, None, &mut words),
+            }
+        } else if word.len() > 1 && word.starts_with('-') && !word[1..].starts_with(|c: char| c.is_ascii_digit()) {
+            let (name, attached) = split_short_flag(word);
+            let bundle: Vec<String> = word[1..].chars().map(|c| format!("-{c}")).collect();
+            if attached.is_empty() || checker.takes_value(name) == Some(true) {
+                let value = attached.strip_prefix('=').unwrap_or(attached);
+                checker.check_flag(line, name, (!attached.is_empty()).then_some(value), &mut words);
+            } else {
+                // Switches bundled as in `-it`.
+                for letter in bundle {
+                    checker.check_flag(line, &letter, None, &mut std::iter::empty());
+                }
+            }
+        } else if in_path {
+            if !checker.enter(line, word) {
+                return;
+            }
+            in_path = !checker.program.children(&checker.path).is_empty();
+        }
+    }
+
+    if !checker.path.is_empty() && checker.program.command(&checker.path).is_none() {
+        let message = format!(
+            "`{}` needs a subcommand: {}",
+            checker.command_name(),
+            checker.program.children(&checker.path).join(", "),
+        );
+        checker.problems.push(Problem::new(last_line, message));
+    }
+}
+
+/// Whether `flag` takes a value in an invocation of `family` (as named by
+/// `program_family`) whose positional words so far are `words`, or `None` if
+/// the tables do not know the flag there.
+pub fn takes_value(family: &str, words: &[String], flag: &str) -> Option<bool> {
+    let program = flags::program(family)?;
+    let mut path = String::new();
+    for word in words {
+        let name = program.resolve(&path, word);
+        let candidate = if path.is_empty() { name.to_string() } else { format!("{path} {name}") };
+        if !program.has_path(&candidate) {
+            break;
+        }
+        path = candidate;
+    }
+    program.takes_value(&path, flag)
+}
+
+/// Checks every invocation of a known tool in `command` and returns what is
+/// wrong with them; an empty list means they look fine.
+pub fn lint_command(command: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut words = Vec::new();
+    for token in tokenize(command) {
+        match token.word {
+            Word::Text(text) => words.push((text, token.line)),
+            Word::Operator(_) => lint_invocation(&std::mem::take(&mut words), &mut problems),
+        }
+    }
+    lint_invocation(&words, &mut problems);
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(command: &str) -> Vec<String> {
+        lint_command(command).into_iter().map(|problem| problem.message).collect()
+    }
+
+    #[test]
+    fn valid_commands_pass() {
+        assert!(messages("k run tmp --rm -it --image=busybox -- sh -c 'echo --nope'").is_empty());
+        assert!(messages("k -n dev get pods -oyaml --field-selector status.phase=Running | grep -i name").is_empty());
+        assert!(messages("k create secret generic db --from-literal=user=admin --dry-run=client -o yaml").is_empty());
+        assert!(messages("helm -n web ls -a && helm repo update").is_empty());
+        assert!(messages("sudo podman run -d --name web nginx > /tmp/out").is_empty());
+    }
+
+    #[test]
+    fn bare_dry_run_is_deprecated() {
+        assert_eq!(
+            messages("k create deploy web --image=nginx --dry-run -o yaml"),
+            ["`--dry-run` without a strategy is deprecated; use `--dry-run=client` or `--dry-run=server`"]
+        );
+        assert_eq!(messages("k apply -f app.yaml --dry-run=true").len(), 1);
+    }
+
+    #[test]
+    fn removed_flags_are_explained() {
+        assert_eq!(
+            messages("k run web --image=nginx --replicas=3"),
+            ["`kubectl run` only creates Pods since 1.18; use `kubectl create deployment` or `create cronjob`"]
+        );
+        assert_eq!(messages("helm install --name web bitnami/nginx").len(), 1);
+    }
+
+    #[test]
+    fn unknown_flags_suggest_a_known_one() {
+        assert_eq!(
+            messages("k create deployment web --imag=nginx"),
+            ["unknown flag `--imag` for `kubectl create deployment` (did you mean `--image`?)"]
+        );
+        assert_eq!(
+            messages("k create deploy web --image=nginx --env A=b"),
+            ["unknown flag `--env` for `kubectl create deployment`"]
+        );
+    }
+
+    #[test]
+    fn non_ascii_short_flags_are_reported() {
+        for command in ["k get pods -é", "k get pods -éx"] {
+            let messages = messages(command);
+            assert!(messages[0].starts_with("unknown flag `-é` for `kubectl get`"), "{messages:?}");
+        }
+        assert_eq!(messages("k get pods -oé"), ["unknown output format `é`"]);
+    }
+
+    #[test]
+    fn flags_depend_on_the_subcommand() {
+        assert!(messages("k logs -f web").is_empty());
+        assert_eq!(messages("k apply -f"), ["`-f` needs a value"]);
+        assert!(messages("docker load -i image.tar").is_empty());
+    }
+
+    #[test]
+    fn subcommands_are_checked() {
+        assert_eq!(
+            messages("k rolout status deploy/web"),
+            ["unknown subcommand `rolout` for `kubectl` (did you mean `rollout`?)"]
+        );
+        assert_eq!(
+            messages("k rollout deploy/web"),
+            ["unknown subcommand `deploy/web` for `kubectl rollout`"]
+        );
+        assert_eq!(messages("helm repo"), ["`helm repo` needs a subcommand: add, list, remove, update"]);
+    }
+
+    #[test]
+    fn output_formats_are_checked() {
+        assert_eq!(messages("k get pods -o yml"), ["unknown output format `yml` (did you mean `yaml`?)"]);
+        assert!(messages("k get pod web -o jsonpath='{.status.phase}'").is_empty());
+    }
+
+    #[test]
+    fn problems_report_their_line() {
+        let problems = lint_command("k get pods\nk delete pod web \\\n  --forse");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 3);
+    }
+
+    #[test]
+    fn other_programs_are_ignored() {
+        assert!(messages("echo -n 'secret' | base64 --whatever").is_empty());
+        assert!(messages("alias k=kubectl").is_empty());
+    }
+}
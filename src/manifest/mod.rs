@@ -0,0 +1,219 @@
+//! Checks for YAML snippets: that they parse, that complete manifests carry
+//! the fields every Kubernetes object needs, and that they match the
+//! embedded Kubernetes schema. Snippets may also be fragments (a
+//! `readinessProbe:` block, a list of `volumes:`) meant to be pasted into a
+//! manifest; those are checked against the schema type they declare.
+
+mod schema;
+mod tree;
+
+pub use tree::{parse, Entry, Node, SyntaxError, Value};
+
+/// Fields every complete manifest must have.
+const MANIFEST_FIELDS: [&str; 3] = ["apiVersion", "kind", "metadata"];
+
+/// Something wrong with a snippet, at a 1-based line of it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    pub line: usize,
+    /// Path of the offending field, e.g. `spec.containers[0].image`, for
+    /// problems found by the schema.
+    pub path: Option<String>,
+    pub message: String,
+    /// A suggested fix, such as the closest valid field name.
+    pub hint: Option<String>,
+}
+
+impl Problem {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Problem { line, path: None, message: message.into(), hint: None }
+    }
+
+    fn at(line: usize, path: &str, message: impl Into<String>) -> Self {
+        Problem { line, path: Some(path.to_string()), message: message.into(), hint: None }
+    }
+
+    fn with_hint(self, hint: Option<String>) -> Self {
+        Problem { hint, ..self }
+    }
+}
+
+/// A document is a complete manifest, rather than a fragment, when it names
+/// its `apiVersion` or `kind`.
+pub fn is_full_manifest(document: &Node) -> bool {
+    document.get("apiVersion").is_some() || document.get("kind").is_some()
+}
+
+/// Parses a YAML snippet and returns everything wrong with its structure;
+/// an empty list means it is fine.
+pub fn check_snippet(text: &str) -> Vec<Problem> {
+    match parse(text) {
+        Ok(documents) => check_documents(&documents),
+        Err(err) => vec![Problem::new(err.line, err.message)],
+    }
+}
+
+/// Like `check_snippet`, and also checks complete manifests against the
+/// schema of their kind and fragments against their declared `fragment`
+/// type. Fragments without a declared type are only checked for structure.
+pub fn validate_snippet(text: &str, fragment: Option<&str>) -> Vec<Problem> {
+    match parse(text) {
+        Ok(documents) => validate_documents(&documents, fragment),
+        Err(err) => vec![Problem::new(err.line, err.message)],
+    }
+}
+
+/// Like `validate_snippet` for pasted YAML in which every document should be
+/// a complete manifest, so fragments are reported rather than let through.
+pub fn validate_manifests(text: &str) -> Vec<Problem> {
+    let documents = match parse(text) {
+        Ok(documents) => documents,
+        Err(err) => return vec![Problem::new(err.line, err.message)],
+    };
+    let mut problems = validate_documents(&documents, None);
+    for document in &documents {
+        if matches!(document.value, Value::Map(_) | Value::Seq(_)) && !is_full_manifest(document) {
+            problems.push(
+                Problem::new(document.line, "document has no `apiVersion` or `kind`")
+                    .with_hint(Some("add them, or check it as a fragment of the type it belongs to".to_string())),
+            );
+        }
+    }
+    problems.sort_by_key(|problem| problem.line);
+    problems
+}
+
+fn validate_documents(documents: &[Node], fragment: Option<&str>) -> Vec<Problem> {
+    let mut problems = check_documents(documents);
+    for document in documents {
+        match &document.value {
+            Value::Map(_) if is_full_manifest(document) => schema::check_manifest(document, &mut problems),
+            Value::Map(_) | Value::Seq(_) => {
+                if let Some(fragment) = fragment {
+                    schema::check_fragment(document, fragment, &mut problems);
+                }
+            }
+            _ => {}
+        }
+    }
+    problems.sort_by_key(|problem| problem.line);
+    problems
+}
+
+fn check_documents(documents: &[Node]) -> Vec<Problem> {
+    if documents.iter().all(|document| document.value == Value::Null) {
+        return vec![Problem::new(1, "contains no YAML")];
+    }
+
+    let mut problems = Vec::new();
+    for document in documents {
+        match &document.value {
+            // An empty document, e.g. after a trailing `---`.
+            Value::Null => {}
+            Value::Map(_) if is_full_manifest(document) => check_header(document, &mut problems),
+            Value::Map(_) | Value::Seq(_) => {}
+            other => problems.push(Problem::new(
+                document.line,
+                format!("document is {}, not a mapping or list", other.describe()),
+            )),
+        }
+        check_duplicate_keys(document, &mut problems);
+    }
+    problems
+}
+
+fn check_header(document: &Node, problems: &mut Vec<Problem>) {
+    for field in MANIFEST_FIELDS {
+        match document.get(field) {
+            None => problems.push(Problem::new(document.line, format!("manifest has no `{field}`"))),
+            Some(node) if field == "metadata" => {
+                if !matches!(node.value, Value::Map(_)) {
+                    problems.push(Problem::new(
+                        node.line,
+                        format!("`metadata` must be a mapping, found {}", node.value.describe()),
+                    ));
+                }
+            }
+            Some(node) => {
+                if node.as_str().is_none_or(str::is_empty) {
+                    problems.push(Problem::new(node.line, format!("`{field}` must be a non-empty string")));
+                }
+            }
+        }
+    }
+}
+
+/// Later duplicates silently win when YAML is loaded, so a repeated key is
+/// almost always a mistake.
+fn check_duplicate_keys(node: &Node, problems: &mut Vec<Problem>) {
+    match &node.value {
+        Value::Map(entries) => {
+            for (index, entry) in entries.iter().enumerate() {
+                if entries[..index].iter().any(|earlier| earlier.key == entry.key) {
+                    problems.push(Problem::new(entry.line, format!("duplicate key `{}`", entry.key)));
+                }
+                check_duplicate_keys(&entry.value, problems);
+            }
+        }
+        Value::Seq(items) => items.iter().for_each(|item| check_duplicate_keys(item, problems)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fragments_only_need_to_parse() {
+        assert!(check_snippet("readinessProbe:\n  exec:\n    command: [cat, /tmp/ready]\n").is_empty());
+        assert!(check_snippet("- name: data\n  emptyDir: {}\n").is_empty());
+    }
+
+    #[test]
+    fn syntax_errors_report_their_line() {
+        let problems = check_snippet("spec:\n  containers:\n  - name: app\n   image: nginx\n");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 4);
+    }
+
+    #[test]
+    fn manifests_need_api_version_kind_and_metadata() {
+        let problems = check_snippet("apiVersion: v1\nkind: Pod\nspec: {}\n");
+        assert_eq!(problems, vec![Problem::new(1, "manifest has no `metadata`")]);
+        assert!(check_snippet("apiVersion: v1\nkind: Pod\nmetadata:\n  name: web\n").is_empty());
+    }
+
+    #[test]
+    fn metadata_must_be_a_mapping() {
+        let problems = check_snippet("apiVersion: v1\nkind: Pod\nmetadata: web\n");
+        assert_eq!(problems[0].line, 3);
+    }
+
+    #[test]
+    fn every_document_of_a_stream_is_checked() {
+        let text = "apiVersion: v1\nkind: Namespace\nmetadata:\n  name: a\n---\nkind: Pod\nmetadata: {}\n";
+        assert_eq!(check_snippet(text), vec![Problem::new(6, "manifest has no `apiVersion`")]);
+    }
+
+    #[test]
+    fn duplicate_keys_are_reported() {
+        let problems = check_snippet("env:\n- name: A\n  value: x\n  value: y\n");
+        assert_eq!(problems, vec![Problem::new(4, "duplicate key `value`")]);
+    }
+
+    #[test]
+    fn pasted_fragments_are_reported() {
+        let text = "apiVersion: v1\nkind: ConfigMap\nmetadata:\n  name: a\n---\nreplicas: 3\n";
+        let problems = validate_manifests(text);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 6);
+        assert!(validate_snippet(text, None).is_empty());
+    }
+
+    #[test]
+    fn plain_values_are_not_snippets() {
+        assert_eq!(check_snippet("just text")[0].message, "document is a string, not a mapping or list");
+        assert_eq!(check_snippet("")[0].message, "contains no YAML");
+    }
+}
@@ -0,0 +1,397 @@
+//! Validation against `data/schema/kubernetes.json`, a trimmed copy of the
+//! Kubernetes OpenAPI (v2) definitions for the kinds the CKAD covers.
+//!
+//! Unknown fields and values of the wrong type are reported with their path,
+//! e.g. `spec.containers[0].readinessProbe.initialDelaySecond`. Required
+//! fields are only enforced for complete manifests, since a fragment shows
+//! just the part of an object that matters.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use super::{Node, Problem, Value};
+use crate::catalog_rules::FRAGMENT_TYPES;
+use crate::spelling::closest;
+
+const SCHEMA_JSON: &str = include_str!("../../data/schema/kubernetes.json");
+
+/// Fragment types that are not a kind, and the definitions they stand for.
+/// Any kind in the schema, such as `Deployment`, is a fragment type too.
+const NAMED_FRAGMENTS: [(&str, &str); 4] = [
+    ("container", "io.k8s.api.core.v1.Container"),
+    ("podSpec", "io.k8s.api.core.v1.PodSpec"),
+    ("probe", "io.k8s.api.core.v1.Probe"),
+    ("volume", "io.k8s.api.core.v1.Volume"),
+];
+
+#[derive(Deserialize)]
+struct SchemaFile {
+    definitions: BTreeMap<String, Schema>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Schema {
+    #[serde(rename = "$ref")]
+    reference: Option<String>,
+    #[serde(rename = "type")]
+    type_name: Option<String>,
+    format: Option<String>,
+    #[serde(default)]
+    properties: BTreeMap<String, Schema>,
+    items: Option<Box<Schema>>,
+    additional_properties: Option<Box<Schema>>,
+    #[serde(default)]
+    required: Vec<String>,
+    #[serde(rename = "x-kubernetes-group-version-kind", default)]
+    group_version_kind: Vec<GroupVersionKind>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GroupVersionKind {
+    group: String,
+    version: String,
+    kind: String,
+}
+
+impl GroupVersionKind {
+    /// The `apiVersion` of the kind, e.g. `apps/v1`, or `v1` for the core
+    /// group.
+    fn api_version(&self) -> String {
+        if self.group.is_empty() {
+            self.version.clone()
+        } else {
+            format!("{}/{}", self.group, self.version)
+        }
+    }
+}
+
+fn definitions() -> &'static BTreeMap<String, Schema> {
+    static DEFINITIONS: OnceLock<BTreeMap<String, Schema>> = OnceLock::new();
+    DEFINITIONS.get_or_init(|| {
+        serde_json::from_str::<SchemaFile>(SCHEMA_JSON)
+            .expect("data/schema/kubernetes.json is not a valid schema")
+            .definitions
+    })
+}
+
+/// The definition of a kind, with the `apiVersion` it is served under.
+fn kind_definition(kind: &str) -> Option<(&'static Schema, String)> {
+    definitions().values().find_map(|schema| {
+        schema
+            .group_version_kind
+            .iter()
+            .find(|gvk| gvk.kind == kind)
+            .map(|gvk| (schema, gvk.api_version()))
+    })
+}
+
+fn fragment_definition(fragment: &str) -> Option<&'static Schema> {
+    match NAMED_FRAGMENTS.iter().find(|(name, _)| *name == fragment) {
+        Some((_, definition)) => definitions().get(*definition),
+        None => kind_definition(fragment).map(|(schema, _)| schema),
+    }
+}
+
+/// Follows `$ref`s to the schema they point at.
+fn resolve(schema: &Schema) -> &Schema {
+    let mut schema = schema;
+    while let Some(reference) = &schema.reference {
+        let name = reference.trim_start_matches("#/definitions/");
+        match definitions().get(name) {
+            Some(target) => schema = target,
+            None => break,
+        }
+    }
+    schema
+}
+
+fn field_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{parent}.{key}")
+    }
+}
+
+/// What a value of the schema's type is called in messages, or `None` if
+/// the value matches.
+fn type_mismatch(schema: &Schema, value: &Value) -> Option<&'static str> {
+    let format = schema.format.as_deref();
+    let matches = match (schema.type_name.as_deref(), value) {
+        // An explicit null leaves the field unset.
+        (_, Value::Null) | (None, _) => true,
+        (Some("object"), Value::Map(_)) | (Some("array"), Value::Seq(_)) => true,
+        (Some("string"), Value::String(_)) | (Some("boolean"), Value::Bool(_)) => true,
+        (Some("integer"), Value::Int(_)) | (Some("number"), Value::Int(_) | Value::Float(_)) => true,
+        (Some("string"), Value::Int(_)) => matches!(format, Some("int-or-string" | "quantity")),
+        (Some("string"), Value::Float(_)) => format == Some("quantity"),
+        _ => false,
+    };
+    if matches {
+        return None;
+    }
+    Some(match (schema.type_name.as_deref(), format) {
+        (Some("string"), Some("int-or-string")) => "an integer or a string",
+        (Some("string"), Some("quantity")) => "a quantity such as `500m` or `1Gi`",
+        (Some("string"), _) => "a string",
+        (Some("integer"), _) => "an integer",
+        (Some("number"), _) => "a number",
+        (Some("boolean"), _) => "a boolean",
+        (Some("array"), _) => "a list",
+        _ => "a mapping",
+    })
+}
+
+/// How to fix the common type mistakes: unquoted numbers and booleans
+/// where a string is expected, and a single item where a list is.
+fn type_hint(schema: &Schema, value: &Value) -> Option<String> {
+    let text = match (schema.type_name.as_deref(), value) {
+        (Some("array"), Value::Map(_)) => return Some("start each item of the list with `- `".to_string()),
+        (Some("string"), Value::Int(number)) => number.to_string(),
+        (Some("string"), Value::Float(number)) => number.to_string(),
+        (Some("string"), Value::Bool(flag)) => flag.to_string(),
+        _ => return None,
+    };
+    Some(format!("quote it: `\"{text}\"`"))
+}
+
+fn check_node(schema: &Schema, node: &Node, path: &str, required: bool, problems: &mut Vec<Problem>) {
+    let schema = resolve(schema);
+    if let Some(expected) = type_mismatch(schema, &node.value) {
+        let what = if path.is_empty() { "the document".to_string() } else { format!("`{path}`") };
+        let message = format!("{what} must be {expected}, found {}", node.value.describe());
+        problems.push(Problem::at(node.line, path, message).with_hint(type_hint(schema, &node.value)));
+        return;
+    }
+
+    match &node.value {
+        Value::Seq(items) => {
+            if let Some(item_schema) = &schema.items {
+                for (index, item) in items.iter().enumerate() {
+                    check_node(item_schema, item, &format!("{path}[{index}]"), required, problems);
+                }
+            }
+        }
+        Value::Map(entries) => {
+            for entry in entries {
+                let entry_path = field_path(path, &entry.key);
+                match (schema.properties.get(&entry.key), schema.additional_properties.as_deref()) {
+                    (Some(property), _) | (None, Some(property)) => {
+                        check_node(property, &entry.value, &entry_path, required, problems)
+                    }
+                    // Without declared properties the object was trimmed
+                    // from the schema, and anything goes.
+                    (None, None) if schema.properties.is_empty() => {}
+                    (None, None) => {
+                        let message = format!("unknown field `{entry_path}`");
+                        let hint = closest(&entry.key, schema.properties.keys().map(String::as_str))
+                            .map(|name| format!("did you mean `{name}`?"));
+                        problems.push(Problem::at(entry.line, &entry_path, message).with_hint(hint));
+                    }
+                }
+            }
+            if required {
+                for field in &schema.required {
+                    if !entries.iter().any(|entry| entry.key == *field) {
+                        let field_path = field_path(path, field);
+                        problems.push(Problem::at(
+                            node.line,
+                            &field_path,
+                            format!("missing required field `{field_path}`"),
+                        ));
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Checks a complete manifest against the definition of its kind.
+pub fn check_manifest(document: &Node, problems: &mut Vec<Problem>) {
+    let (Some(api_version), Some(kind)) = (
+        document.get("apiVersion").and_then(Node::as_str),
+        document.get("kind").and_then(Node::as_str),
+    ) else {
+        // Reported by the header check.
+        return;
+    };
+    let kind_line = document.get("kind").map_or(document.line, |node| node.line);
+    let Some((schema, served_as)) = kind_definition(kind) else {
+        let kinds = definitions().values().flat_map(|schema| &schema.group_version_kind).map(|gvk| gvk.kind.as_str());
+        let hint = match closest(kind, kinds) {
+            Some(name) => format!("did you mean `{name}`?"),
+            None => "only the kinds the CKAD covers are embedded; this one cannot be checked here".to_string(),
+        };
+        let message = format!("kind `{kind}` is not in the embedded schema");
+        problems.push(Problem::at(kind_line, "kind", message).with_hint(Some(hint)));
+        return;
+    };
+    if served_as != api_version {
+        let line = document.get("apiVersion").map_or(document.line, |node| node.line);
+        let message = format!("`{kind}` is not served as `apiVersion: {api_version}`");
+        let hint = format!("use `apiVersion: {served_as}`");
+        problems.push(Problem::at(line, "apiVersion", message).with_hint(Some(hint)));
+    }
+    check_node(schema, document, "", true, problems);
+}
+
+/// Checks a fragment against the definition of its declared type. A list
+/// is checked item by item, so a fragment of type `volume` may also be a
+/// list of volumes.
+pub fn check_fragment(document: &Node, fragment: &str, problems: &mut Vec<Problem>) {
+    let schema = FRAGMENT_TYPES.contains(&fragment).then(|| fragment_definition(fragment)).flatten();
+    let Some(schema) = schema else {
+        problems.push(Problem::new(document.line, format!("unknown fragment type `{fragment}`")));
+        return;
+    };
+    match &document.value {
+        Value::Seq(items) => {
+            for (index, item) in items.iter().enumerate() {
+                check_node(schema, item, &format!("[{index}]"), false, problems);
+            }
+        }
+        _ => check_node(schema, document, "", false, problems),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::validate_snippet;
+
+    fn messages(text: &str, fragment: Option<&str>) -> Vec<String> {
+        validate_snippet(text, fragment).into_iter().map(|problem| problem.message).collect()
+    }
+
+    #[test]
+    fn every_reference_resolves() {
+        fn references(schema: &Schema) -> Vec<&str> {
+            let mut names: Vec<&str> =
+                schema.reference.iter().map(|name| name.trim_start_matches("#/definitions/")).collect();
+            let children = schema.properties.values().chain(schema.items.as_deref());
+            for child in children.chain(schema.additional_properties.as_deref()) {
+                names.extend(references(child));
+            }
+            names
+        }
+        for (name, schema) in definitions() {
+            for reference in references(schema) {
+                assert!(definitions().contains_key(reference), "{name} refers to missing {reference}");
+            }
+        }
+    }
+
+    #[test]
+    fn every_fragment_type_is_in_the_schema() {
+        for fragment in FRAGMENT_TYPES {
+            assert!(fragment_definition(fragment).is_some(), "fragment type `{fragment}` is not in the schema");
+        }
+    }
+
+    #[test]
+    fn unknown_fields_are_reported_with_their_path() {
+        let pod = "apiVersion: v1\nkind: Pod\nmetadata:\n  name: web\nspec:\n  restartpolicy: Never\n  containers:\n  - name: web\n    image: nginx\n";
+        let problems = validate_snippet(pod, None);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 6);
+        assert_eq!(problems[0].path.as_deref(), Some("spec.restartpolicy"));
+
+        let probe = "readinessProbe:\n  httpGet:\n    port: 80\n  initialDelaySecond: 5\n";
+        assert_eq!(messages(probe, Some("container")), ["unknown field `readinessProbe.initialDelaySecond`"]);
+    }
+
+    #[test]
+    fn misspellings_suggest_the_closest_name() {
+        let hints = |text: &str, fragment| -> Vec<Option<String>> {
+            validate_snippet(text, fragment).into_iter().map(|problem| problem.hint).collect()
+        };
+        let probe = "readinessProbe:\n  initialDelaySecond: 5\n  perodSeconds: 10\n  magic: true\n";
+        assert_eq!(
+            hints(probe, Some("container")),
+            [
+                Some("did you mean `initialDelaySeconds`?".to_string()),
+                Some("did you mean `periodSeconds`?".to_string()),
+                None,
+            ]
+        );
+        let kind = "apiVersion: apps/v1\nkind: Deploymnet\nmetadata:\n  name: web\n";
+        assert_eq!(hints(kind, None), [Some("did you mean `Deployment`?".to_string())]);
+    }
+
+    #[test]
+    fn apps_workloads_are_in_the_schema() {
+        let pod_template = concat!(
+            "  selector:\n    matchLabels:\n      app: web\n",
+            "  template:\n    metadata:\n      labels:\n        app: web\n",
+            "    spec:\n      containers:\n      - name: web\n        image: nginx\n",
+        );
+        let manifest = |kind: &str, spec: &str| {
+            format!("apiVersion: apps/v1\nkind: {kind}\nmetadata:\n  name: web\nspec:\n{spec}{pod_template}")
+        };
+        for kind in ["DaemonSet", "ReplicaSet"] {
+            assert!(messages(&manifest(kind, "  minReadySeconds: 5\n"), None).is_empty(), "{kind}");
+        }
+        let stateful = manifest(
+            "StatefulSet",
+            concat!(
+                "  serviceName: web\n  replicas: 2\n",
+                "  updateStrategy:\n    rollingUpdate:\n      partition: 1\n",
+                "  volumeClaimTemplates:\n  - metadata:\n      name: data\n",
+                "    spec:\n      accessModes: [ReadWriteOnce]\n",
+            ),
+        );
+        assert!(messages(&stateful, None).is_empty(), "{:?}", messages(&stateful, None));
+        assert_eq!(messages(&manifest("StatefulSet", ""), None), ["missing required field `spec.serviceName`"]);
+    }
+
+    #[test]
+    fn unknown_kinds_say_why_they_are_not_checked() {
+        let problems = validate_snippet("apiVersion: example.com/v1\nkind: Widget\nmetadata:\n  name: w\n", None);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].hint.as_deref().is_some_and(|hint| hint.contains("cannot be checked")));
+    }
+
+    #[test]
+    fn wrong_types_are_reported() {
+        let env = "env:\n- name: PORT\n  value: 8080\n";
+        assert_eq!(
+            messages(env, Some("container")),
+            ["`env[0].value` must be a string, found an integer"]
+        );
+        assert_eq!(validate_snippet(env, Some("container"))[0].hint.as_deref(), Some("quote it: `\"8080\"`"));
+        assert_eq!(
+            messages("spec:\n  replicas: \"3\"\n", Some("Deployment")),
+            ["`spec.replicas` must be an integer, found a string"]
+        );
+    }
+
+    #[test]
+    fn ports_and_quantities_accept_numbers_and_strings() {
+        let container = "livenessProbe:\n  tcpSocket:\n    port: http\nresources:\n  limits:\n    cpu: 1\n    memory: 128Mi\n";
+        assert!(messages(container, Some("container")).is_empty());
+    }
+
+    #[test]
+    fn lists_are_checked_item_by_item() {
+        let volumes = "- name: data\n  emptyDir: {}\n- name: config\n  configmap:\n    name: app\n";
+        assert_eq!(messages(volumes, Some("volume")), ["unknown field `[1].configmap`"]);
+    }
+
+    #[test]
+    fn api_version_must_match_the_kind() {
+        let deployment = "apiVersion: v1\nkind: Deployment\nmetadata:\n  name: web\nspec:\n  selector: {}\n  template: {}\n";
+        let problems = validate_snippet(deployment, None);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].message, "`Deployment` is not served as `apiVersion: v1`");
+        assert_eq!(problems[0].hint.as_deref(), Some("use `apiVersion: apps/v1`"));
+    }
+
+    #[test]
+    fn required_fields_are_only_enforced_for_manifests() {
+        let job = "apiVersion: batch/v1\nkind: Job\nmetadata:\n  name: once\nspec:\n  completions: 3\n";
+        assert_eq!(messages(job, None), ["missing required field `spec.template`"]);
+        assert!(messages("spec:\n  completions: 3\n", Some("Job")).is_empty());
+    }
+}
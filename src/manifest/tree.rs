@@ -0,0 +1,178 @@
+//! A YAML document tree that remembers the line of every node and mapping
+//! key. `yaml_rust2::YamlLoader` drops positions, so problems found after
+//! parsing could not be pointed at otherwise.
+
+use yaml_rust2::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust2::scanner::{Marker, TScalarStyle};
+use yaml_rust2::Yaml;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+    /// 1-based line where the node starts.
+    pub line: usize,
+    pub value: Value,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Seq(Vec<Node>),
+    Map(Vec<Entry>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub key: String,
+    /// 1-based line of the key.
+    pub line: usize,
+    pub value: Node,
+}
+
+impl Node {
+    /// The value of `key` if this node is a mapping that has it.
+    pub fn get(&self, key: &str) -> Option<&Node> {
+        match &self.value {
+            Value::Map(entries) => entries.iter().find(|entry| entry.key == key).map(|entry| &entry.value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            Value::String(text) => Some(text),
+            _ => None,
+        }
+    }
+}
+
+impl Value {
+    /// How the value is called in messages, e.g. "a mapping".
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::Bool(_) => "a boolean",
+            Value::Int(_) => "an integer",
+            Value::Float(_) => "a number",
+            Value::String(_) => "a string",
+            Value::Seq(_) => "a list",
+            Value::Map(_) => "a mapping",
+        }
+    }
+}
+
+/// A syntax error, with the line it was found on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxError {
+    pub line: usize,
+    pub message: String,
+}
+
+/// A collection under construction: a list, or a mapping with the key that
+/// waits for its value.
+enum Open {
+    Seq(Node),
+    Map(Node, Option<(String, usize)>),
+}
+
+#[derive(Default)]
+struct Builder {
+    documents: Vec<Node>,
+    stack: Vec<Open>,
+    error: Option<SyntaxError>,
+}
+
+impl Builder {
+    fn push(&mut self, node: Node) {
+        let line = node.line;
+        let mut bad_key = false;
+        match self.stack.last_mut() {
+            None => self.documents.push(node),
+            Some(Open::Seq(seq)) => {
+                if let Value::Seq(items) = &mut seq.value {
+                    items.push(node);
+                }
+            }
+            Some(Open::Map(map, pending)) => match pending.take() {
+                Some((key, line)) => {
+                    if let Value::Map(entries) = &mut map.value {
+                        entries.push(Entry { key, line, value: node });
+                    }
+                }
+                None => {
+                    let key = match node.value {
+                        Value::String(text) => text,
+                        Value::Int(number) => number.to_string(),
+                        Value::Bool(flag) => flag.to_string(),
+                        Value::Null => "null".to_string(),
+                        Value::Float(number) => number.to_string(),
+                        Value::Seq(_) | Value::Map(_) => {
+                            bad_key = true;
+                            String::new()
+                        }
+                    };
+                    *pending = Some((key, line));
+                }
+            },
+        }
+        if bad_key {
+            self.fail(line, "mapping keys must be plain values");
+        }
+    }
+
+    fn fail(&mut self, line: usize, message: &str) {
+        self.error.get_or_insert_with(|| SyntaxError { line, message: message.to_string() });
+    }
+}
+
+fn scalar(text: String, style: TScalarStyle) -> Value {
+    if style != TScalarStyle::Plain {
+        return Value::String(text);
+    }
+    match Yaml::from_str(&text) {
+        Yaml::Null => Value::Null,
+        Yaml::Boolean(flag) => Value::Bool(flag),
+        Yaml::Integer(number) => Value::Int(number),
+        Yaml::Real(real) => real.parse().map_or(Value::String(real), Value::Float),
+        _ => Value::String(text),
+    }
+}
+
+impl MarkedEventReceiver for Builder {
+    fn on_event(&mut self, event: Event, mark: Marker) {
+        let line = mark.line();
+        match event {
+            Event::Scalar(text, style, _, _) => self.push(Node { line, value: scalar(text, style) }),
+            Event::SequenceStart(..) => self.stack.push(Open::Seq(Node { line, value: Value::Seq(Vec::new()) })),
+            Event::MappingStart(..) => {
+                self.stack.push(Open::Map(Node { line, value: Value::Map(Vec::new()) }, None))
+            }
+            Event::SequenceEnd | Event::MappingEnd => {
+                if let Some(Open::Seq(node) | Open::Map(node, _)) = self.stack.pop() {
+                    self.push(node);
+                }
+            }
+            Event::Alias(_) => {
+                self.fail(line, "YAML aliases are not supported");
+                self.push(Node { line, value: Value::Null });
+            }
+            Event::Nothing | Event::StreamStart | Event::StreamEnd | Event::DocumentStart | Event::DocumentEnd => {}
+        }
+    }
+}
+
+/// Parses every document of a YAML stream.
+pub fn parse(text: &str) -> Result<Vec<Node>, SyntaxError> {
+    let mut builder = Builder::default();
+    Parser::new_from_str(text).load(&mut builder, true).map_err(|err| SyntaxError {
+        line: err.marker().line(),
+        message: err.info().to_string(),
+    })?;
+    match builder.error {
+        Some(error) => Err(error),
+        None => Ok(builder.documents),
+    }
+}
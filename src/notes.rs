@@ -0,0 +1,137 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::storage;
+
+const STORAGE_KEY: &str = "notes";
+
+/// Version of the exported notes file.
+const EXPORT_VERSION: u32 = 1;
+
+/// The exported notes file: `{ "version": 1, "notes": { "<concept id>": "…" } }`.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NotesFile {
+    version: u32,
+    notes: BTreeMap<String, String>,
+}
+
+/// The reader's own notes, keyed by concept id and persisted in
+/// `localStorage`. Notes of concepts that were since removed are kept, so a
+/// renamed id can be fixed by hand in an export.
+#[derive(Clone, Copy)]
+pub struct Notes {
+    notes: RwSignal<BTreeMap<String, String>>,
+    /// Why the stored notes could not be read, if they could not.
+    load_error: RwSignal<Option<String>>,
+}
+
+impl Notes {
+    /// Loads the notes and makes them available to child components. Stored
+    /// notes that do not parse are never overwritten: the reader can export
+    /// them to fix them, or discard them.
+    pub fn provide() -> Notes {
+        let stored = storage::load_text(STORAGE_KEY).map(|text| serde_json::from_str(&text));
+        let (loaded, load_error) = match stored {
+            Some(Ok(loaded)) => (loaded, None),
+            Some(Err(err)) => (BTreeMap::new(), Some(err.to_string())),
+            None => (BTreeMap::new(), None),
+        };
+        let notes = Notes {
+            notes: create_rw_signal(loaded),
+            load_error: create_rw_signal(load_error),
+        };
+        provide_context(notes);
+        notes
+    }
+
+    pub fn load_error(&self) -> Option<String> {
+        self.load_error.get()
+    }
+
+    /// Refuses changes while the stored notes failed to load, as saving them
+    /// would replace those notes.
+    fn ensure_loaded(&self) -> Result<(), String> {
+        match self.load_error.get_untracked() {
+            Some(_) => Err("Your saved notes could not be loaded; export or discard them first.".to_string()),
+            None => Ok(()),
+        }
+    }
+
+    /// Drops stored notes that failed to load, so that new ones can be saved.
+    pub fn discard_stored(&self) {
+        self.load_error.set(None);
+        self.notes.with_untracked(|notes| storage::save(STORAGE_KEY, notes));
+    }
+
+    pub fn expect() -> Notes {
+        expect_context::<Notes>()
+    }
+
+    pub fn get(&self, id: &str) -> Option<String> {
+        self.notes.with(|notes| notes.get(id).cloned())
+    }
+
+    pub fn with<R>(&self, f: impl FnOnce(&BTreeMap<String, String>) -> R) -> R {
+        self.notes.with(f)
+    }
+
+    /// Saves the note of concept `id`. A blank note deletes it.
+    pub fn set(&self, id: &str, text: &str) -> Result<(), String> {
+        self.ensure_loaded()?;
+        let text = text.trim();
+        self.notes.update(|notes| {
+            if text.is_empty() {
+                notes.remove(id);
+            } else {
+                notes.insert(id.to_string(), text.to_string());
+            }
+            storage::save(STORAGE_KEY, notes);
+        });
+        Ok(())
+    }
+
+    /// All notes as a pretty-printed JSON file, or the stored text as it is
+    /// if it failed to load.
+    pub fn export(&self) -> String {
+        if self.load_error.get_untracked().is_some() {
+            return storage::load_text(STORAGE_KEY).unwrap_or_default();
+        }
+        self.notes.with_untracked(|notes| {
+            let file = NotesFile {
+                version: EXPORT_VERSION,
+                notes: notes.clone(),
+            };
+            serde_json::to_string_pretty(&file).unwrap_or_default()
+        })
+    }
+
+    /// Merges the notes of an exported file into the current ones; a note in
+    /// the file replaces the note of the same concept. Returns the number of
+    /// notes imported.
+    pub fn import(&self, text: &str) -> Result<usize, String> {
+        self.ensure_loaded()?;
+        let file: NotesFile =
+            serde_json::from_str(text).map_err(|err| format!("Not a notes file: {err}"))?;
+        if file.version != EXPORT_VERSION {
+            return Err(format!(
+                "Unsupported notes file version {} (expected {EXPORT_VERSION})",
+                file.version
+            ));
+        }
+
+        let imported: Vec<(String, String)> = file
+            .notes
+            .into_iter()
+            .map(|(id, text)| (id, text.trim().to_string()))
+            .filter(|(_, text)| !text.is_empty())
+            .collect();
+        let count = imported.len();
+
+        self.notes.update(|notes| {
+            notes.extend(imported);
+            storage::save(STORAGE_KEY, notes);
+        });
+        Ok(count)
+    }
+}
@@ -0,0 +1,118 @@
+//! Placeholders in commands: uppercase words such as `POD_NAME` that stand for
+//! a value the reader supplies. Concepts declare their placeholders in the
+//! catalog; this module finds and fills them.
+//!
+//! `build.rs` includes this file to validate the declarations, so it must not
+//! depend on the rest of the crate.
+
+use std::ops::Range;
+
+/// One occurrence of a placeholder in a command.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Slot<'a> {
+    pub range: Range<usize>,
+    pub name: &'a str,
+}
+
+fn is_word_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+/// Byte ranges of every whole-word occurrence of `name` in `text`. `POD` does
+/// not match inside `POD_NAME`, but does match in `pod/POD` or `POD:/tmp`.
+pub fn occurrences(text: &str, name: &str) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    text.match_indices(name)
+        .map(|(start, _)| start..start + name.len())
+        .filter(|range| {
+            let before = range.start.checked_sub(1).map(|index| bytes[index]);
+            let after = bytes.get(range.end).copied();
+            !before.is_some_and(is_word_byte) && !after.is_some_and(is_word_byte)
+        })
+        .collect()
+}
+
+/// Every occurrence of the declared `placeholders` in `text`, in text order.
+pub fn slots<'a>(text: &str, placeholders: &[&'a str]) -> Vec<Slot<'a>> {
+    let mut slots: Vec<Slot<'a>> = placeholders
+        .iter()
+        .flat_map(|name| {
+            occurrences(text, name)
+                .into_iter()
+                .map(move |range| Slot { range, name })
+        })
+        .collect();
+    slots.sort_by_key(|slot| slot.range.start);
+    slots
+}
+
+/// Sorts `slots` by position and drops any slot overlapping an earlier one.
+pub fn merge(mut slots: Vec<Slot>) -> Vec<Slot> {
+    slots.sort_by_key(|slot| (slot.range.start, std::cmp::Reverse(slot.range.end)));
+    let mut end = 0;
+    slots.retain(|slot| {
+        let keep = slot.range.start >= end;
+        if keep {
+            end = slot.range.end;
+        }
+        keep
+    });
+    slots
+}
+
+/// `text` with every slot replaced by its value. Slots without a value keep
+/// the placeholder name.
+pub fn fill(text: &str, slots: &[Slot], value_of: impl Fn(&str) -> Option<String>) -> String {
+    let mut filled = String::with_capacity(text.len());
+    let mut position = 0;
+
+    for slot in slots {
+        filled.push_str(&text[position..slot.range.start]);
+        match value_of(slot.name) {
+            Some(value) => filled.push_str(&value),
+            None => filled.push_str(slot.name),
+        }
+        position = slot.range.end;
+    }
+    filled.push_str(&text[position..]);
+
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn occurrences_match_whole_words_only() {
+        assert_eq!(occurrences("k logs POD_NAME -c POD", "POD").as_slice(), std::slice::from_ref(&(19..22)));
+        assert_eq!(occurrences("k cp pod/POD POD:/tmp", "POD"), [9..12, 13..16]);
+        assert!(occurrences("PODS POD2 _POD", "POD").is_empty());
+    }
+
+    #[test]
+    fn slots_are_in_text_order() {
+        let command = "k exec POD -n NAMESPACE -- cat FILE";
+        let names: Vec<_> = slots(command, &["FILE", "NAMESPACE", "POD"]).into_iter().map(|slot| slot.name).collect();
+        assert_eq!(names, ["POD", "NAMESPACE", "FILE"]);
+    }
+
+    #[test]
+    fn merge_drops_overlapping_slots() {
+        let merged = merge(vec![
+            Slot { range: 5..9, name: "NAME" },
+            Slot { range: 0..9, name: "APP_NAME" },
+            Slot { range: 12..15, name: "POD" },
+        ]);
+        assert_eq!(merged, [Slot { range: 0..9, name: "APP_NAME" }, Slot { range: 12..15, name: "POD" }]);
+    }
+
+    #[test]
+    fn fill_keeps_names_without_a_value() {
+        let command = "k logs POD -n NAMESPACE";
+        let slots = slots(command, &["POD", "NAMESPACE"]);
+        let value_of = |name: &str| (name == "NAMESPACE").then(|| "neptune".to_string());
+        assert_eq!(fill(command, &slots, value_of), "k logs POD -n neptune");
+        assert_eq!(fill(command, &[], value_of), command);
+    }
+}
@@ -0,0 +1,705 @@
+//! The Pod manifest builder: a form model that renders as YAML and, when
+//! every field it uses has a flag, as the `k run` command that prints the
+//! same Pod. Field values stay text as typed, so the form never loses input;
+//! numbers are parsed when rendering and reported when they do not parse.
+
+use serde_json::{json, Map, Value};
+use crate::yaml;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RestartPolicy {
+    #[default]
+    Always,
+    OnFailure,
+    Never,
+}
+
+impl RestartPolicy {
+    pub const ALL: [RestartPolicy; 3] = [RestartPolicy::Always, RestartPolicy::OnFailure, RestartPolicy::Never];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RestartPolicy::Always => "Always",
+            RestartPolicy::OnFailure => "OnFailure",
+            RestartPolicy::Never => "Never",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Label {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EnvSource {
+    #[default]
+    Literal,
+    Secret,
+    ConfigMap,
+}
+
+impl EnvSource {
+    pub const ALL: [EnvSource; 3] = [EnvSource::Literal, EnvSource::Secret, EnvSource::ConfigMap];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            EnvSource::Literal => "value",
+            EnvSource::Secret => "secretKeyRef",
+            EnvSource::ConfigMap => "configMapKeyRef",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EnvVar {
+    pub name: String,
+    pub source: EnvSource,
+    /// The literal value, or the name of the Secret or ConfigMap.
+    pub value: String,
+    /// The key in the Secret or ConfigMap; unused for literals.
+    pub key: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Resources {
+    pub cpu_request: String,
+    pub memory_request: String,
+    pub cpu_limit: String,
+    pub memory_limit: String,
+}
+
+impl Resources {
+    fn is_empty(&self) -> bool {
+        [&self.cpu_request, &self.memory_request, &self.cpu_limit, &self.memory_limit]
+            .iter()
+            .all(|value| value.trim().is_empty())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProbeKind {
+    #[default]
+    None,
+    HttpGet,
+    TcpSocket,
+    Exec,
+}
+
+impl ProbeKind {
+    pub const ALL: [ProbeKind; 4] = [ProbeKind::None, ProbeKind::HttpGet, ProbeKind::TcpSocket, ProbeKind::Exec];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ProbeKind::None => "none",
+            ProbeKind::HttpGet => "httpGet",
+            ProbeKind::TcpSocket => "tcpSocket",
+            ProbeKind::Exec => "exec",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Probe {
+    pub kind: ProbeKind,
+    /// HTTP path, for `httpGet`.
+    pub path: String,
+    /// Port number or name, for `httpGet` and `tcpSocket`.
+    pub port: String,
+    /// Command run through `sh -c`, for `exec`.
+    pub command: String,
+    pub initial_delay_seconds: String,
+    pub period_seconds: String,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VolumeSource {
+    #[default]
+    EmptyDir,
+    ConfigMap,
+    Secret,
+    PersistentVolumeClaim,
+}
+
+impl VolumeSource {
+    pub const ALL: [VolumeSource; 4] = [
+        VolumeSource::EmptyDir,
+        VolumeSource::ConfigMap,
+        VolumeSource::Secret,
+        VolumeSource::PersistentVolumeClaim,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            VolumeSource::EmptyDir => "emptyDir",
+            VolumeSource::ConfigMap => "configMap",
+            VolumeSource::Secret => "secret",
+            VolumeSource::PersistentVolumeClaim => "persistentVolumeClaim",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Volume {
+    pub name: String,
+    pub source: VolumeSource,
+    /// The ConfigMap, Secret or claim mounted; unused for `emptyDir`.
+    pub reference: String,
+    pub mount_path: String,
+}
+
+/// Container-level security settings, the ones the CKAD asks about.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Security {
+    pub run_as_user: String,
+    pub run_as_non_root: bool,
+    pub read_only_root_filesystem: bool,
+    pub no_privilege_escalation: bool,
+    pub drop_all_capabilities: bool,
+}
+
+impl Security {
+    fn is_empty(&self) -> bool {
+        *self == Security::default()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PodForm {
+    pub name: String,
+    pub image: String,
+    pub restart_policy: RestartPolicy,
+    pub labels: Vec<Label>,
+    pub env: Vec<EnvVar>,
+    pub ports: Vec<String>,
+    pub resources: Resources,
+    pub readiness_probe: Probe,
+    pub liveness_probe: Probe,
+    pub volumes: Vec<Volume>,
+    pub security: Security,
+}
+
+/// A DNS-1123 label, as Pod and container names must be.
+fn is_dns_label(text: &str) -> bool {
+    (1..=63).contains(&text.len())
+        && text.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+        && !text.starts_with('-')
+        && !text.ends_with('-')
+}
+
+fn is_port_number(text: &str) -> bool {
+    text.parse::<u16>().is_ok_and(|port| port > 0)
+}
+
+/// An IANA service name, as a port may be named: at most 15 lowercase
+/// letters, digits and single dashes, with at least one letter.
+fn is_port_name(text: &str) -> bool {
+    (1..=15).contains(&text.len())
+        && text.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+        && text.bytes().any(|b| b.is_ascii_lowercase())
+        && !text.starts_with('-')
+        && !text.ends_with('-')
+        && !text.contains("--")
+}
+
+/// A number when the text is one, so that it renders unquoted.
+fn number_or_text(text: &str) -> Value {
+    let text = text.trim();
+    text.parse::<i64>().map_or_else(|_| Value::from(text), Value::from)
+}
+
+fn insert_number(map: &mut Map<String, Value>, key: &str, text: &str) {
+    if !text.trim().is_empty() {
+        map.insert(key.to_string(), number_or_text(text));
+    }
+}
+
+/// `text` as one shell word, single-quoted when it has anything the shell
+/// would interpret.
+fn shell_quote(text: &str) -> String {
+    let is_plain = !text.is_empty()
+        && text.bytes().all(|b| b.is_ascii_alphanumeric() || b"_-./:=,@%+".contains(&b));
+    if is_plain {
+        text.to_string()
+    } else {
+        format!("'{}'", text.replace('\'', r"'\''"))
+    }
+}
+
+impl Probe {
+    fn to_json(&self) -> Option<Value> {
+        let mut probe = Map::new();
+        match self.kind {
+            ProbeKind::None => return None,
+            ProbeKind::HttpGet => {
+                let path = if self.path.trim().is_empty() { "/" } else { self.path.trim() };
+                probe.insert("httpGet".into(), json!({ "path": path, "port": number_or_text(&self.port) }));
+            }
+            ProbeKind::TcpSocket => {
+                probe.insert("tcpSocket".into(), json!({ "port": number_or_text(&self.port) }));
+            }
+            ProbeKind::Exec => {
+                probe.insert("exec".into(), json!({ "command": ["sh", "-c", self.command.trim()] }));
+            }
+        }
+        insert_number(&mut probe, "initialDelaySeconds", &self.initial_delay_seconds);
+        insert_number(&mut probe, "periodSeconds", &self.period_seconds);
+        Some(Value::Object(probe))
+    }
+
+    fn problems(&self, which: &str, problems: &mut Vec<String>) {
+        match self.kind {
+            ProbeKind::None => return,
+            ProbeKind::HttpGet | ProbeKind::TcpSocket if self.port.trim().is_empty() => {
+                problems.push(format!("The {which} probe needs a port."));
+            }
+            ProbeKind::HttpGet | ProbeKind::TcpSocket
+                if !is_port_number(self.port.trim()) && !is_port_name(self.port.trim()) =>
+            {
+                problems.push(format!(
+                    "The {which} probe's port `{}` must be a number from 1 to 65535 or a name such as http.",
+                    self.port.trim()
+                ));
+            }
+            ProbeKind::Exec if self.command.trim().is_empty() => {
+                problems.push(format!("The {which} probe needs a command."));
+            }
+            _ => {}
+        }
+        let path = self.path.trim();
+        if self.kind == ProbeKind::HttpGet && !path.is_empty() && (!path.starts_with('/') || path.contains(' ')) {
+            problems.push(format!("The {which} probe's path must start with / and contain no spaces."));
+        }
+        for (field, text) in [("initial delay", &self.initial_delay_seconds), ("period", &self.period_seconds)] {
+            if !text.trim().is_empty() && text.trim().parse::<u32>().is_err() {
+                problems.push(format!("The {which} probe's {field} must be a whole number of seconds."));
+            }
+        }
+    }
+}
+
+impl PodForm {
+    /// A form for the usual first step: a named Pod running one image.
+    pub fn new() -> Self {
+        PodForm { name: "web".to_string(), image: "nginx:alpine".to_string(), ..PodForm::default() }
+    }
+
+    fn labels_json(&self) -> Value {
+        let labels: Map<String, Value> = self
+            .labels
+            .iter()
+            .filter(|label| !label.key.trim().is_empty())
+            .map(|label| (label.key.trim().to_string(), Value::from(label.value.trim())))
+            .collect();
+        if labels.is_empty() {
+            // What `k run` labels a Pod with when given no labels.
+            json!({ "run": self.name.trim() })
+        } else {
+            Value::Object(labels)
+        }
+    }
+
+    fn container_json(&self) -> Value {
+        let mut container = Map::new();
+        container.insert("name".into(), Value::from(self.name.trim()));
+        container.insert("image".into(), Value::from(self.image.trim()));
+
+        let ports: Vec<Value> = self
+            .ports
+            .iter()
+            .filter(|port| !port.trim().is_empty())
+            .map(|port| json!({ "containerPort": number_or_text(port) }))
+            .collect();
+        if !ports.is_empty() {
+            container.insert("ports".into(), Value::from(ports));
+        }
+
+        let env: Vec<Value> = self
+            .env
+            .iter()
+            .filter(|var| !var.name.trim().is_empty())
+            .map(|var| {
+                let name = var.name.trim();
+                let reference = json!({ "name": var.value.trim(), "key": var.key.trim() });
+                match var.source {
+                    EnvSource::Literal => json!({ "name": name, "value": var.value }),
+                    EnvSource::Secret => json!({ "name": name, "valueFrom": { "secretKeyRef": reference } }),
+                    EnvSource::ConfigMap => json!({ "name": name, "valueFrom": { "configMapKeyRef": reference } }),
+                }
+            })
+            .collect();
+        if !env.is_empty() {
+            container.insert("env".into(), Value::from(env));
+        }
+
+        if !self.resources.is_empty() {
+            let amounts = |cpu: &str, memory: &str| {
+                let mut amounts = Map::new();
+                for (key, text) in [("cpu", cpu), ("memory", memory)] {
+                    if !text.trim().is_empty() {
+                        amounts.insert(key.into(), Value::from(text.trim()));
+                    }
+                }
+                amounts
+            };
+            let mut resources = Map::new();
+            let requests = amounts(&self.resources.cpu_request, &self.resources.memory_request);
+            let limits = amounts(&self.resources.cpu_limit, &self.resources.memory_limit);
+            if !requests.is_empty() {
+                resources.insert("requests".into(), Value::Object(requests));
+            }
+            if !limits.is_empty() {
+                resources.insert("limits".into(), Value::Object(limits));
+            }
+            container.insert("resources".into(), Value::Object(resources));
+        }
+
+        if let Some(probe) = self.readiness_probe.to_json() {
+            container.insert("readinessProbe".into(), probe);
+        }
+        if let Some(probe) = self.liveness_probe.to_json() {
+            container.insert("livenessProbe".into(), probe);
+        }
+
+        let mounts: Vec<Value> = self
+            .volumes
+            .iter()
+            .filter(|volume| !volume.name.trim().is_empty())
+            .map(|volume| json!({ "name": volume.name.trim(), "mountPath": volume.mount_path.trim() }))
+            .collect();
+        if !mounts.is_empty() {
+            container.insert("volumeMounts".into(), Value::from(mounts));
+        }
+
+        if !self.security.is_empty() {
+            let security = &self.security;
+            let mut context = Map::new();
+            insert_number(&mut context, "runAsUser", &security.run_as_user);
+            if security.run_as_non_root {
+                context.insert("runAsNonRoot".into(), Value::from(true));
+            }
+            if security.read_only_root_filesystem {
+                context.insert("readOnlyRootFilesystem".into(), Value::from(true));
+            }
+            if security.no_privilege_escalation {
+                context.insert("allowPrivilegeEscalation".into(), Value::from(false));
+            }
+            if security.drop_all_capabilities {
+                context.insert("capabilities".into(), json!({ "drop": ["ALL"] }));
+            }
+            container.insert("securityContext".into(), Value::Object(context));
+        }
+
+        Value::Object(container)
+    }
+
+    fn volume_json(volume: &Volume) -> Value {
+        let name = volume.name.trim();
+        let reference = volume.reference.trim();
+        match volume.source {
+            VolumeSource::EmptyDir => json!({ "name": name, "emptyDir": {} }),
+            VolumeSource::ConfigMap => json!({ "name": name, "configMap": { "name": reference } }),
+            VolumeSource::Secret => json!({ "name": name, "secret": { "secretName": reference } }),
+            VolumeSource::PersistentVolumeClaim => {
+                json!({ "name": name, "persistentVolumeClaim": { "claimName": reference } })
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut spec = Map::new();
+        spec.insert("containers".into(), json!([self.container_json()]));
+        let volumes: Vec<Value> = self
+            .volumes
+            .iter()
+            .filter(|volume| !volume.name.trim().is_empty())
+            .map(PodForm::volume_json)
+            .collect();
+        if !volumes.is_empty() {
+            spec.insert("volumes".into(), Value::from(volumes));
+        }
+        spec.insert("restartPolicy".into(), Value::from(self.restart_policy.name()));
+
+        json!({
+            "apiVersion": "v1",
+            "kind": "Pod",
+            "metadata": { "name": self.name.trim(), "labels": self.labels_json() },
+            "spec": spec,
+        })
+    }
+
+    /// The manifest, without the `---` the emitter starts documents with.
+    pub fn to_yaml(&self) -> String {
+        let yaml = yaml::emit(&self.to_json());
+        yaml.strip_prefix("---\n").unwrap_or(&yaml).to_string()
+    }
+
+    /// What keeps the form from `k run`: the parts that have no flag.
+    fn needs_yaml(&self) -> Vec<&'static str> {
+        let mut parts = Vec::new();
+        if self.env.iter().any(|var| var.source != EnvSource::Literal && !var.name.trim().is_empty()) {
+            parts.push("env from Secrets or ConfigMaps");
+        }
+        if self.ports.iter().filter(|port| !port.trim().is_empty()).count() > 1 {
+            parts.push("more than one port");
+        }
+        if !self.resources.is_empty() {
+            parts.push("resources");
+        }
+        if self.readiness_probe.kind != ProbeKind::None || self.liveness_probe.kind != ProbeKind::None {
+            parts.push("probes");
+        }
+        if self.volumes.iter().any(|volume| !volume.name.trim().is_empty()) {
+            parts.push("volumes");
+        }
+        if !self.security.is_empty() {
+            parts.push("securityContext");
+        }
+        parts
+    }
+
+    /// The `k run` command that prints the same Pod, or the parts of the
+    /// form it cannot express.
+    pub fn run_command(&self) -> Result<String, Vec<&'static str>> {
+        let needs_yaml = self.needs_yaml();
+        if !needs_yaml.is_empty() {
+            return Err(needs_yaml);
+        }
+        let mut words = vec![
+            "k".to_string(),
+            "run".to_string(),
+            shell_quote(self.name.trim()),
+            format!("--image={}", shell_quote(self.image.trim())),
+        ];
+        let labels: Vec<String> = self
+            .labels
+            .iter()
+            .filter(|label| !label.key.trim().is_empty())
+            .map(|label| format!("{}={}", label.key.trim(), label.value.trim()))
+            .collect();
+        if !labels.is_empty() {
+            words.push(format!("--labels={}", shell_quote(&labels.join(","))));
+        }
+        for var in self.env.iter().filter(|var| !var.name.trim().is_empty()) {
+            words.push(format!("--env={}", shell_quote(&format!("{}={}", var.name.trim(), var.value))));
+        }
+        if let Some(port) = self.ports.iter().find(|port| !port.trim().is_empty()) {
+            words.push(format!("--port={}", shell_quote(port.trim())));
+        }
+        if self.restart_policy != RestartPolicy::Always {
+            words.push(format!("--restart={}", self.restart_policy.name()));
+        }
+        words.push("--dry-run=client".to_string());
+        words.push("-oyaml".to_string());
+        Ok(words.join(" "))
+    }
+
+    /// Everything that would make the Pod invalid, as sentences for the form.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if !is_dns_label(self.name.trim()) {
+            problems.push(
+                "The name must be 1-63 lowercase letters, digits or dashes, starting and ending with a letter or \
+                 digit."
+                    .to_string(),
+            );
+        }
+        if self.image.trim().is_empty() || self.image.trim().contains(char::is_whitespace) {
+            problems.push("The image must be a single word, such as nginx:1.25.".to_string());
+        }
+
+        let mut keys = Vec::new();
+        for label in self.labels.iter().filter(|label| !label.key.trim().is_empty()) {
+            if keys.contains(&label.key.trim()) {
+                problems.push(format!("The label `{}` is set twice.", label.key.trim()));
+            }
+            keys.push(label.key.trim());
+        }
+
+        let mut names = Vec::new();
+        for var in self.env.iter().filter(|var| !var.name.trim().is_empty()) {
+            let name = var.name.trim();
+            if names.contains(&name) {
+                problems.push(format!("The variable `{name}` is set twice."));
+            }
+            names.push(name);
+            if var.source != EnvSource::Literal && (var.value.trim().is_empty() || var.key.trim().is_empty()) {
+                problems.push(format!("The variable `{name}` needs the name of the object and a key."));
+            }
+        }
+
+        for port in self.ports.iter().filter(|port| !port.trim().is_empty()) {
+            if !is_port_number(port.trim()) {
+                problems.push(format!("The port `{}` must be a number from 1 to 65535.", port.trim()));
+            }
+        }
+
+        self.readiness_probe.problems("readiness", &mut problems);
+        self.liveness_probe.problems("liveness", &mut problems);
+
+        let mut volume_names = Vec::new();
+        for volume in self.volumes.iter().filter(|volume| !volume.name.trim().is_empty()) {
+            let name = volume.name.trim();
+            if volume_names.contains(&name) {
+                problems.push(format!("The volume `{name}` is defined twice."));
+            }
+            volume_names.push(name);
+            if volume.source != VolumeSource::EmptyDir && volume.reference.trim().is_empty() {
+                problems.push(format!("The volume `{name}` needs the name of its {}.", volume.source.name()));
+            }
+            if !volume.mount_path.trim().starts_with('/') {
+                problems.push(format!("The volume `{name}` needs an absolute mount path."));
+            }
+        }
+
+        let user = self.security.run_as_user.trim();
+        if !user.is_empty() && user.parse::<u32>().is_err() {
+            problems.push("The user id must be a whole number.".to_string());
+        }
+        problems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lint::lint_command;
+    use crate::manifest::validate_manifests;
+
+    fn full_form() -> PodForm {
+        PodForm {
+            labels: vec![Label { key: "app".into(), value: "web".into() }],
+            env: vec![
+                EnvVar { name: "MODE".into(), value: "production".into(), ..EnvVar::default() },
+                EnvVar { name: "PASSWORD".into(), source: EnvSource::Secret, value: "db".into(), key: "pass".into() },
+                EnvVar { name: "COLOR".into(), source: EnvSource::ConfigMap, value: "ui".into(), key: "color".into() },
+            ],
+            ports: vec!["80".into(), "443".into()],
+            resources: Resources { cpu_request: "100m".into(), memory_limit: "128Mi".into(), ..Resources::default() },
+            readiness_probe: Probe {
+                kind: ProbeKind::HttpGet,
+                path: "/healthz".into(),
+                port: "80".into(),
+                initial_delay_seconds: "5".into(),
+                ..Probe::default()
+            },
+            liveness_probe: Probe { kind: ProbeKind::Exec, command: "cat /tmp/ready".into(), ..Probe::default() },
+            volumes: vec![
+                Volume { name: "cache".into(), mount_path: "/cache".into(), ..Volume::default() },
+                Volume {
+                    name: "config".into(),
+                    source: VolumeSource::ConfigMap,
+                    reference: "web-config".into(),
+                    mount_path: "/etc/web".into(),
+                },
+            ],
+            security: Security {
+                run_as_user: "1000".into(),
+                no_privilege_escalation: true,
+                drop_all_capabilities: true,
+                ..Security::default()
+            },
+            ..PodForm::new()
+        }
+    }
+
+    #[test]
+    fn manifests_pass_the_schema() {
+        for form in [PodForm::new(), full_form()] {
+            assert!(form.problems().is_empty());
+            let yaml = form.to_yaml();
+            assert_eq!(validate_manifests(&yaml), [], "{yaml}");
+        }
+    }
+
+    #[test]
+    fn simple_pods_have_a_run_command() {
+        let form = PodForm {
+            labels: vec![Label { key: "app".into(), value: "web".into() }],
+            env: vec![EnvVar { name: "GREETING".into(), value: "hello world".into(), ..EnvVar::default() }],
+            ports: vec!["80".into()],
+            restart_policy: RestartPolicy::Never,
+            ..PodForm::new()
+        };
+        let command = form.run_command().unwrap();
+        assert_eq!(
+            command,
+            "k run web --image=nginx:alpine --labels=app=web --env='GREETING=hello world' --port=80 --restart=Never \
+             --dry-run=client -oyaml"
+        );
+        assert_eq!(lint_command(&command), []);
+    }
+
+    #[test]
+    fn run_command_names_what_needs_yaml() {
+        assert_eq!(
+            full_form().run_command(),
+            Err(vec![
+                "env from Secrets or ConfigMaps",
+                "more than one port",
+                "resources",
+                "probes",
+                "volumes",
+                "securityContext",
+            ])
+        );
+    }
+
+    #[test]
+    fn blank_env_rows_do_not_need_yaml() {
+        let form = PodForm {
+            env: vec![EnvVar { source: EnvSource::Secret, ..EnvVar::default() }],
+            ..PodForm::new()
+        };
+        assert_eq!(form.run_command(), Ok("k run web --image=nginx:alpine --dry-run=client -oyaml".to_string()));
+    }
+
+    #[test]
+    fn probe_ports_and_paths_are_checked() {
+        let probe = |kind, port: &str, path: &str| PodForm {
+            readiness_probe: Probe { kind, port: port.into(), path: path.into(), ..Probe::default() },
+            ..PodForm::new()
+        };
+        for (port, path) in [("8080", "/healthz"), ("http", ""), ("web-api-2", "/")] {
+            assert_eq!(probe(ProbeKind::HttpGet, port, path).problems(), Vec::<String>::new(), "{port} {path}");
+        }
+        assert_eq!(
+            probe(ProbeKind::TcpSocket, "hello world", "").problems(),
+            ["The readiness probe's port `hello world` must be a number from 1 to 65535 or a name such as http."]
+        );
+        for port in ["0", "65536", "8080", "-http", "a-very-long-port-name", "web--api", "HTTP"] {
+            let problems = probe(ProbeKind::TcpSocket, port, "").problems();
+            assert_eq!(problems.len(), usize::from(port != "8080"), "{port}");
+        }
+        assert_eq!(
+            probe(ProbeKind::HttpGet, "80", "health check").problems(),
+            ["The readiness probe's path must start with / and contain no spaces."]
+        );
+    }
+
+    #[test]
+    fn pods_without_labels_get_the_run_label() {
+        assert!(PodForm::new().to_yaml().contains("labels:\n    run: web\n"));
+    }
+
+    #[test]
+    fn invalid_fields_are_reported() {
+        let form = PodForm {
+            name: "Web_1".into(),
+            ports: vec!["http".into()],
+            volumes: vec![Volume { name: "data".into(), source: VolumeSource::Secret, ..Volume::default() }],
+            ..PodForm::new()
+        };
+        assert_eq!(
+            form.problems(),
+            [
+                "The name must be 1-63 lowercase letters, digits or dashes, starting and ending with a letter or \
+                 digit.",
+                "The port `http` must be a number from 1 to 65535.",
+                "The volume `data` needs the name of its secret.",
+                "The volume `data` needs an absolute mount path.",
+            ]
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::ckad_data::Concept;
+use crate::storage;
+
+const STORAGE_KEY: &str = "progress";
+
+/// How far the reader has got with a concept.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    #[default]
+    New,
+    Practicing,
+    Mastered,
+}
+
+impl Status {
+    pub const ALL: [Status; 3] = [Status::New, Status::Practicing, Status::Mastered];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Status::New => "New",
+            Status::Practicing => "Practicing",
+            Status::Mastered => "Mastered",
+        }
+    }
+
+    /// CSS class of the status, e.g. `status-practicing`.
+    pub fn class(self) -> &'static str {
+        match self {
+            Status::New => "status-new",
+            Status::Practicing => "status-practicing",
+            Status::Mastered => "status-mastered",
+        }
+    }
+}
+
+/// Number of concepts in each status.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProgressCounts {
+    pub new: usize,
+    pub practicing: usize,
+    pub mastered: usize,
+}
+
+impl ProgressCounts {
+    pub fn total(&self) -> usize {
+        self.new + self.practicing + self.mastered
+    }
+
+    pub fn count(&self, status: Status) -> usize {
+        match status {
+            Status::New => self.new,
+            Status::Practicing => self.practicing,
+            Status::Mastered => self.mastered,
+        }
+    }
+
+    /// Share of `status` in percent, for bar widths.
+    pub fn percent(&self, status: Status) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => self.count(status) as f64 * 100.0 / total as f64,
+        }
+    }
+
+    /// Readiness in percent: mastered concepts count fully, concepts being
+    /// practiced count half.
+    pub fn readiness(&self) -> f64 {
+        self.percent(Status::Mastered) + self.percent(Status::Practicing) / 2.0
+    }
+}
+
+/// The study status of each concept, keyed by concept id and persisted in
+/// `localStorage`. Concepts without an entry are new.
+#[derive(Clone, Copy)]
+pub struct Progress(RwSignal<BTreeMap<String, Status>>);
+
+impl Progress {
+    /// Loads the statuses and makes them available to child components.
+    pub fn provide() -> Progress {
+        let statuses: BTreeMap<String, Status> = storage::load(STORAGE_KEY).unwrap_or_default();
+        let progress = Progress(create_rw_signal(statuses));
+        provide_context(progress);
+        progress
+    }
+
+    pub fn expect() -> Progress {
+        expect_context::<Progress>()
+    }
+
+    pub fn status(&self, id: &str) -> Status {
+        self.0.with(|statuses| statuses.get(id).copied().unwrap_or_default())
+    }
+
+    pub fn set(&self, id: &str, status: Status) {
+        self.0.update(|statuses| {
+            if status == Status::New {
+                statuses.remove(id);
+            } else {
+                statuses.insert(id.to_string(), status);
+            }
+            storage::save(STORAGE_KEY, statuses);
+        });
+    }
+
+    pub fn counts<'a>(&self, concepts: impl IntoIterator<Item = &'a Concept>) -> ProgressCounts {
+        self.0.with(|statuses| {
+            let mut counts = ProgressCounts::default();
+            for concept in concepts {
+                match statuses.get(concept.id).copied().unwrap_or_default() {
+                    Status::New => counts.new += 1,
+                    Status::Practicing => counts.practicing += 1,
+                    Status::Mastered => counts.mastered += 1,
+                }
+            }
+            counts
+        })
+    }
+}
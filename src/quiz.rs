@@ -0,0 +1,202 @@
+//! Multiple-choice quizzes generated from the catalog. Each question asks
+//! for a concept's command given its title, or the other way round, with
+//! wrong choices taken from other concepts of the same category. The quiz is
+//! fully determined by its seed, so `#/quiz/<seed>` reproduces it.
+
+use crate::ckad_data::{Category, Concept, ConceptKind};
+use crate::drill::{check, is_single_line};
+use crate::rng::Rng;
+
+/// Questions in one quiz.
+pub const QUESTION_COUNT: usize = 10;
+
+/// Wrong choices per question, when the category has enough concepts.
+const MAX_DISTRACTORS: usize = 3;
+
+/// Fewer wrong choices than this would make a question a coin toss.
+const MIN_DISTRACTORS: usize = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuestionKind {
+    /// "Which command does X?" — the choices are commands.
+    Command,
+    /// "What does this command do?" — the choices are titles.
+    Purpose,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Question {
+    pub kind: QuestionKind,
+    pub category: &'static Category,
+    pub concept: &'static Concept,
+    pub choices: Vec<&'static str>,
+    /// Index of the right choice.
+    pub answer: usize,
+}
+
+impl Question {
+    /// What the question shows: the title or the command.
+    pub fn prompt(&self) -> &'static str {
+        match self.kind {
+            QuestionKind::Command => self.concept.title,
+            QuestionKind::Purpose => self.concept.command,
+        }
+    }
+}
+
+/// Only one-line shell commands make readable choices.
+fn is_quizzable(concept: &Concept) -> bool {
+    concept.kind == ConceptKind::Shell && is_single_line(concept.command)
+}
+
+fn choice_text(kind: QuestionKind, concept: &'static Concept) -> &'static str {
+    match kind {
+        QuestionKind::Command => concept.command,
+        QuestionKind::Purpose => concept.title,
+    }
+}
+
+/// Whether either command is an accepted spelling of the other, in which
+/// case one cannot be a wrong answer for the other.
+fn same_command(a: &Concept, b: &Concept) -> bool {
+    check(a.command, b.command, b.equivalents, b.placeholders).correct
+        || check(b.command, a.command, a.equivalents, a.placeholders).correct
+}
+
+/// Other concepts of the category that are clearly wrong answers for
+/// `concept`: distinct from the answer and from each other.
+fn distractors(
+    kind: QuestionKind,
+    category: &'static Category,
+    concept: &'static Concept,
+) -> Vec<&'static Concept> {
+    let answer = choice_text(kind, concept);
+    let mut picked: Vec<&'static Concept> = Vec::new();
+    for other in category.concepts.iter().filter(|other| is_quizzable(other)) {
+        let text = choice_text(kind, other);
+        if text != answer
+            && !same_command(concept, other)
+            && picked.iter().all(|&seen| choice_text(kind, seen) != text)
+        {
+            picked.push(other);
+        }
+    }
+    picked
+}
+
+/// Generates up to `count` questions from `catalog`. The same seed and
+/// catalog always give the same quiz.
+pub fn generate(catalog: &'static [Category], seed: u64, count: usize) -> Vec<Question> {
+    let mut rng = Rng::new(seed);
+    let mut concepts: Vec<(&'static Category, &'static Concept)> = catalog
+        .iter()
+        .flat_map(|category| category.concepts.iter().map(move |concept| (category, concept)))
+        .filter(|(_, concept)| is_quizzable(concept))
+        .collect();
+    rng.shuffle(&mut concepts);
+
+    let mut questions = Vec::with_capacity(count);
+    for (category, concept) in concepts {
+        if questions.len() == count {
+            break;
+        }
+        let kind = if rng.below(2) == 0 { QuestionKind::Command } else { QuestionKind::Purpose };
+        let mut wrong = distractors(kind, category, concept);
+        if wrong.len() < MIN_DISTRACTORS {
+            continue;
+        }
+        rng.shuffle(&mut wrong);
+        let mut choices: Vec<&'static str> = wrong
+            .into_iter()
+            .take(MAX_DISTRACTORS)
+            .map(|other| choice_text(kind, other))
+            .collect();
+        let answer = rng.below(choices.len() + 1);
+        choices.insert(answer, choice_text(kind, concept));
+
+        questions.push(Question { kind, category, concept, choices, answer });
+    }
+    questions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ckad_data::get_ckad_concepts;
+
+    fn quizzes() -> impl Iterator<Item = Vec<Question>> {
+        (0..50).map(|seed| generate(get_ckad_concepts(), seed, QUESTION_COUNT))
+    }
+
+    #[test]
+    fn same_seed_gives_same_quiz() {
+        let quiz = |seed| generate(get_ckad_concepts(), seed, QUESTION_COUNT);
+        assert_eq!(quiz(42), quiz(42));
+    }
+
+    #[test]
+    fn different_seeds_give_different_quizzes() {
+        let quiz = |seed| generate(get_ckad_concepts(), seed, QUESTION_COUNT);
+        assert_ne!(quiz(1), quiz(2));
+    }
+
+    #[test]
+    fn quizzes_are_full_length() {
+        for quiz in quizzes() {
+            assert_eq!(quiz.len(), QUESTION_COUNT);
+        }
+    }
+
+    #[test]
+    fn answer_is_the_concepts_own_text() {
+        for question in quizzes().flatten() {
+            assert_eq!(question.choices[question.answer], choice_text(question.kind, question.concept));
+        }
+    }
+
+    #[test]
+    fn distractors_are_never_identical_to_the_answer() {
+        for question in quizzes().flatten() {
+            let answer = question.choices[question.answer];
+            for (index, choice) in question.choices.iter().enumerate() {
+                if index != question.answer {
+                    assert_ne!(*choice, answer, "distractor repeats the answer of `{}`", question.concept.id);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn distractors_are_not_equivalent_commands() {
+        for question in quizzes().flatten() {
+            for other in question.category.concepts {
+                let offered = question.choices.contains(&choice_text(question.kind, other));
+                if other.id != question.concept.id && offered {
+                    assert!(
+                        !same_command(question.concept, other),
+                        "`{}` is offered as wrong for `{}`",
+                        other.id,
+                        question.concept.id
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn choices_are_distinct_and_from_the_same_category() {
+        for question in quizzes().flatten() {
+            let mut choices = question.choices.clone();
+            choices.sort_unstable();
+            choices.dedup();
+            assert_eq!(choices.len(), question.choices.len());
+            assert!((MIN_DISTRACTORS + 1..=MAX_DISTRACTORS + 1).contains(&choices.len()));
+            for choice in &question.choices {
+                let from_category = question.category.concepts.iter().any(|concept| {
+                    choice_text(question.kind, concept) == *choice
+                });
+                assert!(from_category);
+            }
+        }
+    }
+}
@@ -0,0 +1,243 @@
+use leptos::*;
+
+/// A location inside the wiki, stored in the URL fragment so that links can
+/// be shared and back/forward navigation works without a server.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Route {
+    Home,
+    Concept(String),
+    Category(String),
+    Flashcards,
+    Drill,
+    Exam,
+    /// A quiz; the seed is left out until one is started.
+    Quiz(Option<u64>),
+    Validate,
+    PodBuilder,
+}
+
+impl Route {
+    /// Parses a fragment such as `#/concept/rollback-to-specific-revision`.
+    /// Anything unrecognised falls back to `Route::Home`.
+    pub fn from_hash(hash: &str) -> Route {
+        let path = hash.trim_start_matches('#').trim_start_matches('/');
+        let mut parts = path.splitn(2, '/');
+
+        match (parts.next(), parts.next()) {
+            (Some("concept"), Some(id)) if !id.is_empty() => Route::Concept(id.to_string()),
+            (Some("category"), Some(id)) if !id.is_empty() => Route::Category(id.to_string()),
+            (Some("flashcards"), None) => Route::Flashcards,
+            (Some("drill"), None) => Route::Drill,
+            (Some("exam"), None) => Route::Exam,
+            (Some("quiz"), None) => Route::Quiz(None),
+            (Some("quiz"), Some(seed)) => Route::Quiz(seed.parse().ok()),
+            (Some("validate"), None) => Route::Validate,
+            (Some("pod-builder"), None) => Route::PodBuilder,
+            _ => Route::Home,
+        }
+    }
+
+    pub fn to_hash(&self) -> String {
+        match self {
+            Route::Home => "#/".to_string(),
+            Route::Concept(id) => format!("#/concept/{id}"),
+            Route::Category(id) => format!("#/category/{id}"),
+            Route::Flashcards => "#/flashcards".to_string(),
+            Route::Drill => "#/drill".to_string(),
+            Route::Exam => "#/exam".to_string(),
+            Route::Quiz(None) => "#/quiz".to_string(),
+            Route::Quiz(Some(seed)) => format!("#/quiz/{seed}"),
+            Route::Validate => "#/validate".to_string(),
+            Route::PodBuilder => "#/pod-builder".to_string(),
+        }
+    }
+
+    /// Whether the route shows the catalog, as opposed to a study mode.
+    pub fn is_wiki(&self) -> bool {
+        matches!(self, Route::Home | Route::Concept(_) | Route::Category(_))
+    }
+}
+
+/// Reads the route from the current `window.location`.
+pub fn current_route() -> Route {
+    window()
+        .location()
+        .hash()
+        .map(|hash| Route::from_hash(&hash))
+        .unwrap_or(Route::Home)
+}
+
+/// Navigates to `route`, pushing a browser history entry. The resulting
+/// `hashchange` event is what updates the route signal.
+pub fn navigate(route: &Route) {
+    if current_route() != *route {
+        let _ = window().location().set_hash(&route.to_hash());
+    }
+}
+
+/// Creates a signal that follows the URL fragment.
+pub fn create_route_signal() -> ReadSignal<Route> {
+    let (route, set_route) = create_signal(current_route());
+
+    window_event_listener(ev::hashchange, move |_| {
+        set_route.set(current_route());
+    });
+
+    route
+}
+
+/// Search state mirrored into the query string, e.g. `?q=probe&cat=storage`,
+/// so that a filtered view survives reloads and can be shared.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FilterParams {
+    pub query: String,
+    pub category: Option<String>,
+}
+
+impl FilterParams {
+    pub fn from_search(search: &str) -> FilterParams {
+        let Ok(params) = web_sys::UrlSearchParams::new_with_str(search) else {
+            return FilterParams::default();
+        };
+
+        FilterParams {
+            query: params.get("q").unwrap_or_default(),
+            category: params.get("cat").filter(|cat| !cat.is_empty()),
+        }
+    }
+
+    /// Renders the query string including the leading `?`, or an empty
+    /// string when no filter is active.
+    pub fn to_search(&self) -> String {
+        let Ok(params) = web_sys::UrlSearchParams::new() else {
+            return String::new();
+        };
+        if !self.query.is_empty() {
+            params.append("q", &self.query);
+        }
+        if let Some(category) = &self.category {
+            params.append("cat", category);
+        }
+
+        let search = String::from(params.to_string());
+        if search.is_empty() {
+            search
+        } else {
+            format!("?{search}")
+        }
+    }
+}
+
+/// Reads the filter parameters from the current `window.location`.
+pub fn current_filter_params() -> FilterParams {
+    window()
+        .location()
+        .search()
+        .map(|search| FilterParams::from_search(&search))
+        .unwrap_or_default()
+}
+
+/// Writes `params` into the query string, keeping the path and route.
+///
+/// Changing the category or starting/clearing a search pushes a history entry.
+/// Further keystrokes in an ongoing search replace the current entry, so the
+/// back button does not step through the query one character at a time.
+pub fn write_filter_params(params: &FilterParams, previous: Option<&FilterParams>) {
+    if current_filter_params() == *params {
+        return;
+    }
+
+    let location = window().location();
+    let url = format!(
+        "{}{}{}",
+        location.pathname().unwrap_or_default(),
+        params.to_search(),
+        location.hash().unwrap_or_default(),
+    );
+
+    let is_typing = previous.is_some_and(|previous| {
+        previous.category == params.category
+            && !previous.query.is_empty()
+            && !params.query.is_empty()
+    });
+
+    let Ok(history) = window().history() else {
+        return;
+    };
+    let _ = if is_typing {
+        history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&url))
+    } else {
+        history.push_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&url))
+    };
+}
+
+/// Smoothly scrolls the element with the given DOM id into the middle of the
+/// viewport, once the current render has been flushed.
+pub fn scroll_to_element(element_id: String) {
+    request_animation_frame(move || {
+        if let Some(element) = document().get_element_by_id(&element_id) {
+            let options = web_sys::ScrollIntoViewOptions::new();
+            options.set_behavior(web_sys::ScrollBehavior::Smooth);
+            options.set_block(web_sys::ScrollLogicalPosition::Center);
+            element.scroll_into_view_with_scroll_into_view_options(&options);
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_round_trip_through_their_hash() {
+        let routes = [
+            Route::Home,
+            Route::Concept("rollback-to-specific-revision".to_string()),
+            Route::Category("storage".to_string()),
+            Route::Flashcards,
+            Route::Drill,
+            Route::Exam,
+            Route::Quiz(None),
+            Route::Quiz(Some(42)),
+            Route::Quiz(Some(u64::MAX)),
+            Route::Validate,
+            Route::PodBuilder,
+        ];
+        for route in routes {
+            assert_eq!(Route::from_hash(&route.to_hash()), route, "{}", route.to_hash());
+        }
+    }
+
+    #[test]
+    fn hashes_parse_leniently() {
+        assert_eq!(Route::from_hash(""), Route::Home);
+        assert_eq!(Route::from_hash("#"), Route::Home);
+        assert_eq!(Route::from_hash("drill"), Route::Drill);
+        assert_eq!(Route::from_hash("#/quiz/7"), Route::Quiz(Some(7)));
+        assert_eq!(Route::from_hash("#/concept/a/b"), Route::Concept("a/b".to_string()));
+    }
+
+    #[test]
+    fn bad_quiz_seeds_start_a_new_quiz() {
+        assert_eq!(Route::from_hash("#/quiz/abc"), Route::Quiz(None));
+        assert_eq!(Route::from_hash("#/quiz/"), Route::Quiz(None));
+        assert_eq!(Route::from_hash("#/quiz/-1"), Route::Quiz(None));
+    }
+
+    #[test]
+    fn unknown_hashes_and_empty_ids_go_home() {
+        assert_eq!(Route::from_hash("#/concept/"), Route::Home);
+        assert_eq!(Route::from_hash("#/category/"), Route::Home);
+        assert_eq!(Route::from_hash("#/nowhere"), Route::Home);
+        assert_eq!(Route::from_hash("#/drill/extra"), Route::Home);
+    }
+
+    #[test]
+    fn only_catalog_routes_are_wiki_routes() {
+        assert!(Route::Home.is_wiki());
+        assert!(Route::Concept("x".to_string()).is_wiki());
+        assert!(Route::Category("x".to_string()).is_wiki());
+        assert!(!Route::Quiz(None).is_wiki());
+        assert!(!Route::PodBuilder.is_wiki());
+    }
+}
@@ -0,0 +1,133 @@
+//! Inverted index from tokens to the concepts containing them.
+//!
+//! The index for the built-in catalog is generated by `build.rs` and lives in
+//! static memory; `SearchIndex::build` produces the same structure at runtime
+//! for any other set of concepts, and `from_documents` for loose text such as
+//! notes. A query can search several indexes that share concept ordinals.
+
+use super::tokens::invert;
+use super::{Field, Term};
+use crate::ckad_data::Concept;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+/// One occurrence of a token: the concept ordinal and the field it is in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Posting {
+    pub concept: u32,
+    pub field: Field,
+}
+
+/// A distinct token and every place it occurs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexEntry {
+    pub token: Cow<'static, str>,
+    pub postings: Cow<'static, [Posting]>,
+}
+
+/// Tokens of a set of concepts, sorted, with their postings. Concepts are
+/// identified by their ordinal in the order they were indexed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchIndex {
+    concept_count: usize,
+    entries: Cow<'static, [IndexEntry]>,
+}
+
+/// Field of each slot number produced by `tokens::invert`.
+const FIELD_SLOTS: [Field; 4] = [Field::Title, Field::Description, Field::Command, Field::Note];
+
+include!(concat!(env!("OUT_DIR"), "/search_index.rs"));
+
+impl SearchIndex {
+    /// The index of the built-in catalog, in `catalog_concepts()` order.
+    pub fn catalog() -> &'static SearchIndex {
+        &CATALOG_INDEX
+    }
+
+    pub fn build<'a>(concepts: impl IntoIterator<Item = &'a Concept>) -> SearchIndex {
+        let mut concept_count = 0;
+        let mut documents = Vec::new();
+
+        for (ordinal, concept) in concepts.into_iter().enumerate() {
+            let ordinal = ordinal as u32;
+            concept_count += 1;
+            documents.push((ordinal, Field::Title, concept.title));
+            if let Some(description) = &concept.description {
+                documents.push((ordinal, Field::Description, *description));
+            }
+            documents.push((ordinal, Field::Command, concept.command));
+        }
+
+        SearchIndex::from_documents(concept_count, documents)
+    }
+
+    /// Indexes `(concept ordinal, field, text)` triples for a set of
+    /// `concept_count` concepts, not all of which need to have text.
+    pub fn from_documents<'a>(
+        concept_count: usize,
+        documents: impl IntoIterator<Item = (u32, Field, &'a str)>,
+    ) -> SearchIndex {
+        let documents = documents.into_iter().map(|(ordinal, field, text)| {
+            let slot = FIELD_SLOTS.iter().position(|slot| *slot == field).unwrap_or_default();
+            (ordinal, slot as u8, text)
+        });
+
+        let entries = invert(documents)
+            .into_iter()
+            .map(|(token, postings)| IndexEntry {
+                token: Cow::Owned(token),
+                postings: postings
+                    .into_iter()
+                    .map(|(concept, slot)| Posting {
+                        concept,
+                        field: FIELD_SLOTS[slot as usize],
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        SearchIndex {
+            concept_count,
+            entries: Cow::Owned(entries),
+        }
+    }
+
+    pub fn concept_count(&self) -> usize {
+        self.concept_count
+    }
+
+    /// The best weighted match score of `term` in each concept it matches,
+    /// looking only at `field` when given. Every distinct token is compared
+    /// with the term, as substring and typo matches cannot be looked up in
+    /// the sorted entries.
+    pub(super) fn term_scores(&self, term: &Term, field: Option<Field>) -> HashMap<u32, u32> {
+        let mut scores: HashMap<u32, u32> = HashMap::new();
+
+        for entry in self.entries.iter() {
+            let Some(quality) = term.match_quality(&entry.token) else {
+                continue;
+            };
+            for posting in entry.postings.iter() {
+                if field.is_some_and(|scoped| scoped != posting.field) {
+                    continue;
+                }
+                let score = scores.entry(posting.concept).or_default();
+                *score = (*score).max(quality * posting.field.weight());
+            }
+        }
+
+        scores
+    }
+
+    /// Concepts in which `term` matches exactly or as a prefix, looking only
+    /// at `field` when given.
+    pub(super) fn strict_matches(&self, term: &Term, field: Option<Field>) -> HashSet<u32> {
+        self.entries
+            .iter()
+            .filter(|entry| term.matches_strictly(&entry.token))
+            .flat_map(|entry| entry.postings.iter())
+            .filter(|posting| field.is_none_or(|scoped| scoped == posting.field))
+            .map(|posting| posting.concept)
+            .collect()
+    }
+}
@@ -0,0 +1,430 @@
+//! Fuzzy, ranked search over the concept catalog.
+//!
+//! A query is split into terms and every term must match somewhere in a
+//! concept (in any order). Each term contributes the score of its best match,
+//! weighted by the field it was found in, so title hits rank first. Terms
+//! are expanded through Kubernetes short names and plurals (see `synonyms`),
+//! and `query` adds field qualifiers, phrases and exclusions on top. Personal
+//! notes are searched like any other field, through an index built at runtime.
+
+mod fuzzy;
+mod index;
+mod query;
+mod synonyms;
+mod tokens;
+
+use crate::ckad_data::{Category, Concept};
+use fuzzy::allowed_typos;
+pub(crate) use fuzzy::edit_distance;
+use std::ops::Range;
+
+pub use index::SearchIndex;
+pub use query::SearchQuery;
+pub use tokens::{token_spans, tokenize};
+
+/// The concept fields that take part in search, in decreasing weight. The
+/// declaration order matches the field slots used by `tokens::invert`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field {
+    Title,
+    Description,
+    Command,
+    /// The reader's own note, which is not part of the catalog.
+    Note,
+}
+
+impl Field {
+    fn weight(self) -> u32 {
+        match self {
+            Field::Title => 3,
+            Field::Description => 2,
+            Field::Command => 2,
+            Field::Note => 1,
+        }
+    }
+}
+
+/// What a query may read of one concept beyond the index: its category for
+/// `cat:` filters, and its text for phrases and highlighting.
+#[derive(Clone, Copy)]
+pub struct Document<'a> {
+    pub category_id: &'a str,
+    pub concept: &'a Concept,
+    pub note: Option<&'a str>,
+}
+
+/// Match quality of a single term against a single token, out of 100.
+const EXACT: u32 = 100;
+const PREFIX: u32 = 80;
+const SUBSTRING: u32 = 60;
+const ONE_TYPO: u32 = 50;
+const TWO_TYPOS: u32 = 35;
+
+/// Percentage of the match quality kept when a synonym matched rather than
+/// the term as typed, so literal matches rank slightly higher.
+const SYNONYM_PERCENT: u32 = 90;
+
+/// Synonyms up to this many characters, such as `po` or `ev`, only match
+/// whole tokens. As prefixes they would match `port` or `every`.
+const SHORT_SYNONYM_LEN: usize = 3;
+
+/// Extra score when the plain words of the query appear verbatim in the title.
+const TITLE_PHRASE_BONUS: u32 = 100;
+
+/// How well `term` matches `token`, or `None` if it does not match at all.
+pub fn match_quality(term: &str, token: &str) -> Option<u32> {
+    if token == term {
+        return Some(EXACT);
+    }
+    if token.starts_with(term) {
+        return Some(PREFIX);
+    }
+    if term.len() >= 3 && token.contains(term) {
+        return Some(SUBSTRING);
+    }
+
+    let distance = edit_distance(term, token, allowed_typos(term))?;
+    Some(if distance <= 1 { ONE_TYPO } else { TWO_TYPOS })
+}
+
+/// One query term together with the spellings it may stand for.
+#[derive(Clone, Debug, PartialEq)]
+struct Term {
+    spellings: Vec<String>,
+}
+
+impl Term {
+    fn new(text: &str) -> Term {
+        Term {
+            spellings: synonyms::expand(text),
+        }
+    }
+
+    /// Match quality of each spelling against `token`, with whether it is
+    /// the term as typed. The other spellings do not tolerate typos, and
+    /// short ones must be the whole token.
+    fn qualities<'a>(&'a self, token: &'a str) -> impl Iterator<Item = (u32, bool)> + 'a {
+        self.spellings.iter().enumerate().filter_map(move |(index, spelling)| {
+            let quality = match_quality(spelling, token)?;
+            let least = match index {
+                0 => 0,
+                _ if spelling.chars().count() <= SHORT_SYNONYM_LEN => EXACT,
+                _ => SUBSTRING,
+            };
+            (quality >= least).then_some((quality, index == 0))
+        })
+    }
+
+    /// Best match quality of any spelling against `token`. The first
+    /// spelling is the term as typed; the others are discounted.
+    fn match_quality(&self, token: &str) -> Option<u32> {
+        self.qualities(token)
+            .map(|(quality, typed)| if typed { quality } else { quality * SYNONYM_PERCENT / 100 })
+            .max()
+    }
+
+    /// Exact or prefix match of any spelling. Exclusions use this instead of
+    /// `match_quality`: a typo-tolerant `-helm` would hide unrelated cards.
+    fn matches_strictly(&self, token: &str) -> bool {
+        self.qualities(token).any(|(quality, _)| quality >= PREFIX)
+    }
+}
+
+/// Byte ranges of the text that matched a query, per field. Ranges are
+/// sorted and never overlap.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MatchSpans {
+    pub title: Vec<Range<usize>>,
+    pub description: Vec<Range<usize>>,
+    pub command: Vec<Range<usize>>,
+    pub note: Vec<Range<usize>>,
+}
+
+impl MatchSpans {
+    fn field_mut(&mut self, field: Field) -> &mut Vec<Range<usize>> {
+        match field {
+            Field::Title => &mut self.title,
+            Field::Description => &mut self.description,
+            Field::Command => &mut self.command,
+            Field::Note => &mut self.note,
+        }
+    }
+
+    fn normalize(&mut self) {
+        for spans in [&mut self.title, &mut self.description, &mut self.command, &mut self.note] {
+            spans.sort_by_key(|span| span.start);
+            let mut merged: Vec<Range<usize>> = Vec::with_capacity(spans.len());
+            for span in spans.drain(..) {
+                match merged.last_mut() {
+                    Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+                    _ => merged.push(span),
+                }
+            }
+            *spans = merged;
+        }
+    }
+}
+
+/// Splits `text` into consecutive segments, flagging the ones covered by
+/// `spans` (as produced by `MatchSpans`).
+pub fn segments<'a>(text: &'a str, spans: &[Range<usize>]) -> Vec<(&'a str, bool)> {
+    let mut segments = Vec::with_capacity(spans.len() * 2 + 1);
+    let mut position = 0;
+
+    for span in spans {
+        if span.start > position {
+            segments.push((&text[position..span.start], false));
+        }
+        segments.push((&text[span.clone()], true));
+        position = span.end;
+    }
+    if position < text.len() {
+        segments.push((&text[position..], false));
+    }
+
+    segments
+}
+
+/// A concept that matched a query, with its relevance score and the parts
+/// of its text that matched. The concept itself is borrowed from the catalog.
+#[derive(Clone, PartialEq)]
+pub struct SearchHit {
+    pub category: &'static Category,
+    pub concept: &'static Concept,
+    pub score: u32,
+    pub matches: MatchSpans,
+}
+
+/// The hits of one category, best match first.
+#[derive(Clone, PartialEq)]
+pub struct CategoryHits {
+    pub category: &'static Category,
+    pub hits: Vec<SearchHit>,
+}
+
+/// Sorts hits by descending score. The sort is stable, so equally relevant
+/// concepts keep their catalog order.
+pub fn rank(hits: &mut [SearchHit]) {
+    hits.sort_by_key(|hit| std::cmp::Reverse(hit.score));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ckad_data::ConceptKind;
+
+    const fn concept(
+        id: &'static str,
+        title: &'static str,
+        description: &'static str,
+        command: &'static str,
+    ) -> Concept {
+        Concept {
+            id,
+            title,
+            command,
+            description: Some(description),
+            kind: ConceptKind::Shell,
+            placeholders: &[],
+            equivalents: &[],
+            fragment: None,
+        }
+    }
+
+    /// A small catalog for the search tests of this module and its children.
+    pub(super) static CATEGORIES: [Category; 2] = [
+        Category {
+            id: "workloads",
+            name: "🚀 Workload Basics",
+            concepts: &[
+                concept(
+                    "scale-deployment",
+                    "Scale a deployment",
+                    "Change the number of replicas.",
+                    "k scale deploy web --replicas=3",
+                ),
+                concept(
+                    "rollback-revision",
+                    "Rollback to a revision",
+                    "Undo a bad rollout of a deployment.",
+                    "k rollout undo deploy/web --to-revision=2",
+                ),
+                concept(
+                    "generate-pod-yaml",
+                    "Generate pod YAML",
+                    "Write a manifest without creating it.",
+                    "k run web --image=nginx --dry-run=client -o yaml",
+                ),
+            ],
+        },
+        Category {
+            id: "storage",
+            name: "💾 Persistent Storage",
+            concepts: &[
+                Concept {
+                    kind: ConceptKind::Yaml,
+                    ..concept(
+                        "claim-storage",
+                        "Claim persistent storage",
+                        "A claim for a volume.",
+                        "kind: PersistentVolumeClaim\nspec:\n  storageClassName: fast",
+                    )
+                },
+                concept("install-chart", "Install a chart", "Helm for a release.", "helm install web bitnami/nginx"),
+            ],
+        },
+    ];
+
+    fn documents(categories: &'static [Category]) -> Vec<(&'static Category, &'static Concept)> {
+        categories
+            .iter()
+            .flat_map(|category| category.concepts.iter().map(move |concept| (category, concept)))
+            .collect()
+    }
+
+    /// Runs `text` against `categories` with `notes` as `(concept id, note)`,
+    /// returning the hits best first.
+    pub(super) fn search_with_notes(
+        categories: &'static [Category],
+        text: &str,
+        notes: &[(&str, &'static str)],
+    ) -> Vec<SearchHit> {
+        let documents = documents(categories);
+        let note_of = |concept: &Concept| notes.iter().find(|(id, _)| *id == concept.id).map(|&(_, note)| note);
+        let document_at = |ordinal: usize| {
+            let (category, concept) = documents[ordinal];
+            Document { category_id: category.id, concept, note: note_of(concept) }
+        };
+
+        let index = SearchIndex::build(documents.iter().map(|&(_, concept)| concept));
+        let notes_index = SearchIndex::from_documents(
+            documents.len(),
+            documents
+                .iter()
+                .enumerate()
+                .filter_map(|(ordinal, (_, concept))| Some((ordinal as u32, Field::Note, note_of(concept)?))),
+        );
+        let query = SearchQuery::parse(text, &categories.iter().collect::<Vec<_>>());
+        let mut hits: Vec<SearchHit> = query
+            .search(&[&index, &notes_index], document_at)
+            .into_iter()
+            .map(|(ordinal, score)| {
+                let (category, concept) = documents[ordinal];
+                SearchHit { category, concept, score, matches: query.match_spans(document_at(ordinal)) }
+            })
+            .collect();
+        rank(&mut hits);
+        hits
+    }
+
+    /// Ids of the concepts of the test catalog matching `text`, best first.
+    pub(super) fn search(text: &str) -> Vec<&'static str> {
+        search_with_notes(&CATEGORIES, text, &[]).iter().map(|hit| hit.concept.id).collect()
+    }
+
+    #[test]
+    fn edit_distance_counts_transpositions_as_one_edit() {
+        assert_eq!(edit_distance("rollout", "rollout", 2), Some(0));
+        assert_eq!(edit_distance("rollout", "rolout", 2), Some(1));
+        assert_eq!(edit_distance("deployment", "deplyoment", 2), Some(1));
+        assert_eq!(edit_distance("secret", "scerte", 2), Some(2));
+    }
+
+    #[test]
+    fn edit_distance_gives_up_beyond_max() {
+        assert_eq!(edit_distance("deployment", "deplyoment", 0), None);
+        assert_eq!(edit_distance("secret", "scerte", 1), None);
+        assert_eq!(edit_distance("pod", "persistentvolume", 2), None);
+        assert_eq!(edit_distance("abcdef", "ghijkl", 5), None);
+        assert_eq!(edit_distance("abcdef", "ghijkl", 6), Some(6));
+    }
+
+    #[test]
+    fn short_terms_must_match_exactly() {
+        assert_eq!(allowed_typos("pod"), 0);
+        assert_eq!(allowed_typos("helm"), 1);
+        assert_eq!(allowed_typos("rollout"), 1);
+        assert_eq!(allowed_typos("rollback"), 2);
+        assert_eq!(match_quality("pud", "pod"), None);
+    }
+
+    #[test]
+    fn match_quality_prefers_closer_matches() {
+        let qualities = [
+            match_quality("deploy", "deploy"),
+            match_quality("deploy", "deployment"),
+            match_quality("ploy", "deployment"),
+            match_quality("deplyo", "deploy"),
+            match_quality("deplyomnet", "deployment"),
+        ];
+        assert_eq!(qualities, [Some(EXACT), Some(PREFIX), Some(SUBSTRING), Some(ONE_TYPO), Some(TWO_TYPOS)]);
+    }
+
+    #[test]
+    fn typos_still_find_concepts() {
+        assert_eq!(search("rollbak"), ["rollback-revision"]);
+        assert_eq!(search("hlem"), ["install-chart"]);
+        assert_eq!(search("persistnet storage"), ["claim-storage"]);
+    }
+
+    #[test]
+    fn every_term_must_match() {
+        assert_eq!(search("scale replicas"), ["scale-deployment"]);
+        assert_eq!(search("scale helm"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn title_hits_outrank_description_and_command_hits() {
+        static RANKING: [Category; 1] = [Category {
+            id: "services",
+            name: "Services",
+            concepts: &[
+                concept("in-command", "Publish a port", "Make pods reachable.", "k expose deploy web --port=80"),
+                concept("in-description", "Publish a port too", "Expose pods inside.", "k create svc clusterip web"),
+                concept("in-title", "Expose a deployment", "Make pods reachable.", "k create svc nodeport web"),
+            ],
+        }];
+
+        let hits = search_with_notes(&RANKING, "expose", &[]);
+        let ids: Vec<_> = hits.iter().map(|hit| hit.concept.id).collect();
+        assert_eq!(ids, ["in-title", "in-command", "in-description"]);
+        assert!(hits[0].score > hits[1].score);
+        // Equal scores keep catalog order.
+        assert_eq!(hits[1].score, hits[2].score);
+    }
+
+    #[test]
+    fn short_synonyms_only_match_whole_words() {
+        static SHORT_FORMS: [Category; 1] = [Category {
+            id: "short-forms",
+            name: "Short forms",
+            concepts: &[
+                concept("list-pods", "List pods", "Show them all.", "k get po"),
+                concept("tcp-probe", "Liveness probe - TCP socket", "Restart when the port closes.", "k apply -f -"),
+                concept("set-namespace", "Set namespace", "Used by every later command.", "k config set-context"),
+                concept("no-headers", "Hide the header", "Print none of it, not even names.", "k get ns"),
+            ],
+        }];
+        let ids = |text| -> Vec<&str> {
+            search_with_notes(&SHORT_FORMS, text, &[]).iter().map(|hit| hit.concept.id).collect()
+        };
+
+        assert_eq!(ids("pod"), ["list-pods"]);
+        assert!(ids("events").is_empty());
+        assert!(ids("nodes").is_empty());
+        // `ns` is still found through `namespace`.
+        assert_eq!(ids("namespaces"), ["set-namespace", "no-headers"]);
+
+        let hits = search_with_notes(&SHORT_FORMS, "pods", &[]);
+        assert_eq!(hits[0].matches.command, std::slice::from_ref(&(6..8)));
+    }
+
+    #[test]
+    fn segments_split_text_around_spans() {
+        assert_eq!(
+            segments("k scale deploy", &[2..7, 8..14]),
+            [("k ", false), ("scale", true), (" ", false), ("deploy", true)]
+        );
+        assert_eq!(segments("helm", &[]), [("helm", false)]);
+    }
+}
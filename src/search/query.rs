@@ -0,0 +1,547 @@
+//! Structured query syntax.
+//!
+//! Besides plain words, a query may contain:
+//!
+//! - `title:rollback`, `desc:…`, `cmd:…`, `note:…` to search a single field
+//! - `"dry-run=client"` for an exact phrase (also `cmd:"-o yaml"`)
+//! - `-helm` or `-"helm repo"` to exclude concepts that match
+//! - `cat:storage` to restrict to categories, matched by id or by name with
+//!   the emoji ignored, and `kind:yaml` / `kind:shell`
+//!
+//! Malformed parts never make the whole query fail: they are reported as
+//! `QueryProblem`s and the rest of the query still applies.
+
+use super::{
+    synonyms, token_spans, tokenize, Document, Field, MatchSpans, SearchIndex, Term, EXACT,
+    TITLE_PHRASE_BONUS,
+};
+use crate::ckad_data::{Category, ConceptKind};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Something in the query that could not be applied as written.
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueryProblem {
+    UnterminatedQuote,
+    UnknownQualifier(String),
+    EmptyValue(String),
+    UnknownKind(String),
+    UnknownCategory(String),
+}
+
+impl fmt::Display for QueryProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryProblem::UnterminatedQuote => {
+                write!(f, "Missing closing quote: the rest of the query is searched as one phrase.")
+            }
+            QueryProblem::UnknownQualifier(name) => write!(
+                f,
+                "Unknown qualifier \"{name}:\" was searched as plain text. \
+                 Use title:, desc:, cmd:, note:, cat: or kind:."
+            ),
+            QueryProblem::EmptyValue(name) => {
+                write!(f, "\"{name}:\" needs a value, for example {name}:storage.")
+            }
+            QueryProblem::UnknownKind(value) => {
+                write!(f, "\"kind:{value}\" was ignored. Use kind:yaml or kind:shell.")
+            }
+            QueryProblem::UnknownCategory(value) => {
+                write!(f, "No category matches \"cat:{value}\", so it was ignored.")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Matcher {
+    /// Every term must match.
+    Terms(Vec<Term>),
+    /// Lowercase text that must appear verbatim.
+    Phrase(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Clause {
+    /// `None` searches every field.
+    field: Option<Field>,
+    matcher: Matcher,
+    negated: bool,
+}
+
+/// A parsed query, ready to be scored against concepts.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchQuery {
+    clauses: Vec<Clause>,
+    categories: Vec<String>,
+    excluded_categories: Vec<String>,
+    kind: Option<ConceptKind>,
+    /// The plain words of the query, for the title phrase bonus.
+    phrase: String,
+    problems: Vec<QueryProblem>,
+}
+
+impl SearchQuery {
+    /// Parses `text`. `categories` are used to resolve `cat:` qualifiers.
+    pub fn parse(text: &str, categories: &[&Category]) -> SearchQuery {
+        let mut query = SearchQuery::default();
+        let (words, unterminated) = split_words(text);
+        if unterminated {
+            query.problems.push(QueryProblem::UnterminatedQuote);
+        }
+
+        let mut plain_words = Vec::new();
+        for word in &words {
+            let (negated, word) = match word.strip_prefix('-') {
+                Some(rest) if !rest.is_empty() && !rest.starts_with('-') => (true, rest),
+                _ => (false, word.as_str()),
+            };
+
+            if let Some((name, value)) = split_qualifier(word) {
+                let field = match name.to_lowercase().as_str() {
+                    "title" => Some(Field::Title),
+                    "desc" | "description" => Some(Field::Description),
+                    "cmd" | "command" => Some(Field::Command),
+                    "note" | "notes" => Some(Field::Note),
+                    "cat" | "category" => {
+                        query.add_category(name, value, negated, categories);
+                        continue;
+                    }
+                    "kind" => {
+                        query.set_kind(name, value);
+                        continue;
+                    }
+                    _ => {
+                        query.problems.push(QueryProblem::UnknownQualifier(name.to_string()));
+                        None
+                    }
+                };
+
+                if field.is_some() {
+                    if value.is_empty() {
+                        query.problems.push(QueryProblem::EmptyValue(name.to_string()));
+                    } else {
+                        query.add_clause(field, value, negated);
+                    }
+                    continue;
+                }
+            }
+
+            if !negated && !word.starts_with('"') {
+                plain_words.push(word);
+            }
+            query.add_clause(None, word, negated);
+        }
+
+        query.phrase = plain_words.join(" ").to_lowercase();
+        query
+    }
+
+    pub fn problems(&self) -> &[QueryProblem] {
+        &self.problems
+    }
+
+    /// True when the query neither searches nor filters anything.
+    pub fn is_empty(&self) -> bool {
+        self.clauses.is_empty()
+            && self.categories.is_empty()
+            && self.excluded_categories.is_empty()
+            && self.kind.is_none()
+    }
+
+    /// Runs the query against the concepts covered by `indexes`, which share
+    /// concept ordinals (the catalog index and the notes index).
+    /// `document_at(ordinal)` returns what the query may read of the concept
+    /// behind an ordinal. Returns `(ordinal, score)` for every match, in
+    /// ordinal order.
+    ///
+    /// Terms are looked up in the indexes instead of re-tokenizing every
+    /// concept; only phrases and the title bonus read the concept text, and
+    /// only for concepts that survived the term lookups.
+    pub fn search<'a>(
+        &self,
+        indexes: &[&SearchIndex],
+        document_at: impl Fn(usize) -> Document<'a>,
+    ) -> Vec<(usize, u32)> {
+        let mut required = Vec::new();
+        let mut excluded = Vec::new();
+        for clause in &self.clauses {
+            let Matcher::Terms(terms) = &clause.matcher else {
+                continue;
+            };
+            if clause.negated {
+                excluded.push(strict_matches(indexes, terms, clause.field));
+            } else {
+                required.extend(terms.iter().map(|term| term_scores(indexes, term, clause.field)));
+            }
+        }
+
+        // Only concepts matched by every required term can qualify, so walk
+        // the smallest posting set instead of the whole catalog.
+        let concept_count = indexes.iter().map(|index| index.concept_count()).max().unwrap_or(0);
+        let candidates: Vec<usize> = match required.iter().min_by_key(|scores| scores.len()) {
+            Some(scores) => {
+                let mut ordinals: Vec<usize> = scores.keys().map(|&ordinal| ordinal as usize).collect();
+                ordinals.sort_unstable();
+                ordinals
+            }
+            None => (0..concept_count).collect(),
+        };
+
+        candidates
+            .into_iter()
+            .filter_map(|ordinal| {
+                let key = ordinal as u32;
+                let document = document_at(ordinal);
+                let category_id = document.category_id;
+
+                if !self.categories.is_empty() && !self.categories.iter().any(|id| id == category_id) {
+                    return None;
+                }
+                if self.excluded_categories.iter().any(|id| id == category_id) {
+                    return None;
+                }
+                if self.kind.is_some_and(|kind| kind != document.concept.kind) {
+                    return None;
+                }
+                if excluded.iter().any(|matches| matches.contains(&key)) {
+                    return None;
+                }
+
+                let mut total = 0;
+                for scores in &required {
+                    total += scores.get(&key)?;
+                }
+                for clause in &self.clauses {
+                    let Matcher::Phrase(phrase) = &clause.matcher else {
+                        continue;
+                    };
+                    match (phrase_score(document, clause.field, phrase), clause.negated) {
+                        (Some(score), false) => total += score,
+                        (None, false) | (Some(_), true) => return None,
+                        (None, true) => {}
+                    }
+                }
+
+                if !self.phrase.is_empty() && document.concept.title.to_lowercase().contains(&self.phrase) {
+                    total += TITLE_PHRASE_BONUS;
+                }
+
+                Some((ordinal, total))
+            })
+            .collect()
+    }
+
+    /// The parts of a document matched by the positive clauses, for
+    /// highlighting. Only meaningful for documents that `search` returned.
+    pub fn match_spans(&self, document: Document) -> MatchSpans {
+        let mut spans = MatchSpans::default();
+        for clause in self.clauses.iter().filter(|clause| !clause.negated) {
+            for (field, text) in fields(document) {
+                if clause.field.is_some_and(|scoped| scoped != field) {
+                    continue;
+                }
+                let out = spans.field_mut(field);
+
+                match &clause.matcher {
+                    Matcher::Phrase(phrase) => out.extend(phrase_spans(text, phrase)),
+                    Matcher::Terms(terms) => out.extend(
+                        token_spans(text)
+                            .filter(|(_, token)| {
+                                let token = token.to_lowercase();
+                                terms.iter().any(|term| term.match_quality(&token).is_some())
+                            })
+                            .map(|(range, _)| range),
+                    ),
+                }
+            }
+        }
+
+        spans.normalize();
+        spans
+    }
+
+    fn add_clause(&mut self, field: Option<Field>, value: &str, negated: bool) {
+        let matcher = match value.strip_prefix('"') {
+            Some(phrase) => {
+                let phrase = phrase.strip_suffix('"').unwrap_or(phrase).to_lowercase();
+                if phrase.trim().is_empty() {
+                    return;
+                }
+                Matcher::Phrase(phrase)
+            }
+            None => {
+                let terms: Vec<Term> = tokenize(value).iter().map(|term| Term::new(term)).collect();
+                if terms.is_empty() {
+                    return;
+                }
+                Matcher::Terms(terms)
+            }
+        };
+
+        self.clauses.push(Clause { field, matcher, negated });
+    }
+
+    fn add_category(&mut self, name: &str, value: &str, negated: bool, categories: &[&Category]) {
+        let value = value.trim_matches('"');
+        if value.is_empty() {
+            self.problems.push(QueryProblem::EmptyValue(name.to_string()));
+            return;
+        }
+
+        let matching: Vec<String> = categories
+            .iter()
+            .filter(|category| category_matches(category, value))
+            .map(|category| category.id.to_string())
+            .collect();
+
+        if matching.is_empty() {
+            self.problems.push(QueryProblem::UnknownCategory(value.to_string()));
+        } else if negated {
+            self.excluded_categories.extend(matching);
+        } else {
+            self.categories.extend(matching);
+        }
+    }
+
+    fn set_kind(&mut self, name: &str, value: &str) {
+        self.kind = match value.trim_matches('"').to_lowercase().as_str() {
+            "" => {
+                self.problems.push(QueryProblem::EmptyValue(name.to_string()));
+                return;
+            }
+            "yaml" | "yml" | "manifest" => Some(ConceptKind::Yaml),
+            "shell" | "sh" | "cli" => Some(ConceptKind::Shell),
+            _ => {
+                self.problems.push(QueryProblem::UnknownKind(value.to_string()));
+                return;
+            }
+        };
+    }
+}
+
+/// Best weighted match score of `term` in each concept it matches across
+/// `indexes`, looking only at `field` when given.
+fn term_scores(indexes: &[&SearchIndex], term: &Term, field: Option<Field>) -> HashMap<u32, u32> {
+    let mut scores: HashMap<u32, u32> = HashMap::new();
+    for index in indexes {
+        for (ordinal, score) in index.term_scores(term, field) {
+            let best = scores.entry(ordinal).or_default();
+            *best = (*best).max(score);
+        }
+    }
+    scores
+}
+
+/// Concepts in which every one of `terms` matches exactly or as a prefix in
+/// any of `indexes`, looking only at `field` when given.
+fn strict_matches(indexes: &[&SearchIndex], terms: &[Term], field: Option<Field>) -> HashSet<u32> {
+    let mut matches: Option<HashSet<u32>> = None;
+
+    for term in terms {
+        let term_matches: HashSet<u32> = indexes
+            .iter()
+            .flat_map(|index| index.strict_matches(term, field))
+            .collect();
+
+        matches = Some(match matches {
+            Some(previous) => previous.intersection(&term_matches).copied().collect(),
+            None => term_matches,
+        });
+    }
+
+    matches.unwrap_or_default()
+}
+
+/// The searched fields of a document with their text.
+fn fields(document: Document<'_>) -> [(Field, &str); 4] {
+    let concept = document.concept;
+    [
+        (Field::Title, concept.title),
+        (Field::Description, concept.description.unwrap_or_default()),
+        (Field::Command, concept.command),
+        (Field::Note, document.note.unwrap_or_default()),
+    ]
+}
+
+/// Best weighted score of `phrase` over the searched fields of a document.
+fn phrase_score(document: Document, field: Option<Field>, phrase: &str) -> Option<u32> {
+    fields(document)
+        .into_iter()
+        .filter(|(searched, text)| {
+            field.is_none_or(|scoped| scoped == *searched) && text.to_lowercase().contains(phrase)
+        })
+        .map(|(searched, _)| EXACT * searched.weight())
+        .max()
+}
+
+/// Byte ranges of every occurrence of the lowercase `phrase` in `text`,
+/// ignoring case. Text whose lowercase form changes byte length (rare
+/// non-ASCII letters) cannot be mapped back, so it yields no ranges.
+fn phrase_spans(text: &str, phrase: &str) -> Vec<std::ops::Range<usize>> {
+    let lowered = text.to_lowercase();
+    if lowered.len() != text.len() {
+        return Vec::new();
+    }
+
+    lowered
+        .match_indices(phrase)
+        .map(|(start, matched)| start..start + matched.len())
+        .filter(|span| text.is_char_boundary(span.start) && text.is_char_boundary(span.end))
+        .collect()
+}
+
+/// Splits on whitespace outside double quotes. Quotes are kept in the words.
+/// Also reports whether the last quote was left open.
+fn split_words(text: &str) -> (Vec<String>, bool) {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in text.chars() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        if c.is_whitespace() && !in_quotes {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    (words, in_quotes)
+}
+
+/// Splits `name:value` when `name` is a plain word, so that text such as
+/// `"a:b"` or `key=value:NoSchedule` is not mistaken for a qualifier.
+fn split_qualifier(word: &str) -> Option<(&str, &str)> {
+    let (name, value) = word.split_once(':')?;
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    Some((name, value))
+}
+
+/// `cat:` matches a category id, or any part of its name with the emoji and
+/// punctuation removed, so `cat:storage`, `cat:net` and `cat:"rbac"` all work.
+/// Resource short names are expanded, so `cat:svc` finds "Services".
+fn category_matches(category: &Category, value: &str) -> bool {
+    let value = value.to_lowercase();
+    if category.id == value {
+        return true;
+    }
+
+    let name = tokenize(category.name).join(" ");
+    let wanted = tokenize(&value).join(" ");
+    if wanted.is_empty() {
+        return false;
+    }
+
+    synonyms::expand(&wanted)
+        .iter()
+        .any(|spelling| name.contains(spelling.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::tests::{search, search_with_notes, CATEGORIES};
+
+    fn problems(text: &str) -> Vec<QueryProblem> {
+        SearchQuery::parse(text, &CATEGORIES.iter().collect::<Vec<_>>()).problems
+    }
+
+    #[test]
+    fn qualifiers_search_a_single_field() {
+        assert_eq!(search("title:deployment"), ["scale-deployment"]);
+        assert_eq!(search("desc:deployment"), ["rollback-revision"]);
+        assert_eq!(search("cmd:nginx"), ["generate-pod-yaml", "install-chart"]);
+        assert_eq!(search("title:nginx"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn notes_are_searched_like_other_fields() {
+        let notes = [("rollback-revision", "asked in my last mock exam")];
+        let ids = |text| -> Vec<_> {
+            search_with_notes(&CATEGORIES, text, &notes).iter().map(|hit| hit.concept.id).collect()
+        };
+        assert_eq!(ids("mock"), ["rollback-revision"]);
+        assert_eq!(ids("note:mock"), ["rollback-revision"]);
+        assert_eq!(ids("note:rollback"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn quoted_phrases_match_verbatim() {
+        assert_eq!(search("\"dry-run=client\""), ["generate-pod-yaml"]);
+        assert_eq!(search("cmd:\"-o yaml\""), ["generate-pod-yaml"]);
+        assert_eq!(search("title:\"-o yaml\""), Vec::<&str>::new());
+        assert_eq!(search("\"deployment scale\""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn negated_terms_and_phrases_exclude_concepts() {
+        assert_eq!(search("deployment -rollback"), ["scale-deployment"]);
+        assert_eq!(search("web -\"helm install\""), ["scale-deployment", "rollback-revision", "generate-pod-yaml"]);
+        // A double dash starts a flag, not an exclusion.
+        assert_eq!(search("--replicas"), ["scale-deployment"]);
+    }
+
+    #[test]
+    fn categories_match_by_id_or_name() {
+        assert_eq!(search("cat:storage"), ["claim-storage", "install-chart"]);
+        assert_eq!(search("cat:persistent"), ["claim-storage", "install-chart"]);
+        assert_eq!(search("cat:storage helm"), ["install-chart"]);
+        assert_eq!(search("-cat:workloads"), ["claim-storage", "install-chart"]);
+    }
+
+    #[test]
+    fn kind_filters_yaml_or_shell() {
+        assert_eq!(search("kind:yaml"), ["claim-storage"]);
+        assert_eq!(search("kind:sh cat:storage"), ["install-chart"]);
+    }
+
+    #[test]
+    fn malformed_parts_are_reported() {
+        assert_eq!(problems("\"dry-run"), [QueryProblem::UnterminatedQuote]);
+        assert_eq!(problems("foo:bar"), [QueryProblem::UnknownQualifier("foo".to_string())]);
+        assert_eq!(problems("title:"), [QueryProblem::EmptyValue("title".to_string())]);
+        assert_eq!(problems("cat:"), [QueryProblem::EmptyValue("cat".to_string())]);
+        assert_eq!(problems("kind:json"), [QueryProblem::UnknownKind("json".to_string())]);
+        assert_eq!(problems("cat:network"), [QueryProblem::UnknownCategory("network".to_string())]);
+        assert_eq!(problems("key=value:NoSchedule \"a:b\""), []);
+    }
+
+    #[test]
+    fn the_rest_of_a_malformed_query_still_applies() {
+        assert_eq!(search("kind:json rollback"), ["rollback-revision"]);
+        // An unknown qualifier is searched as plain text.
+        assert_eq!(search("bitnami:nginx"), ["install-chart"]);
+    }
+
+    #[test]
+    fn empty_queries_match_everything() {
+        let categories: Vec<_> = CATEGORIES.iter().collect();
+        assert!(SearchQuery::parse("  ", &categories).is_empty());
+        assert!(!SearchQuery::parse("kind:yaml", &categories).is_empty());
+        assert_eq!(search("").len(), 5);
+    }
+
+    #[test]
+    fn match_spans_cover_matched_words_and_phrases() {
+        let hits = search_with_notes(&CATEGORIES, "scale", &[]);
+        let spans = &hits[0].matches;
+        assert_eq!((spans.title.len(), spans.title[0].clone()), (1, 0..5));
+        assert_eq!((spans.command.len(), spans.command[0].clone()), (1, 2..7));
+
+        let hits = search_with_notes(&CATEGORIES, "cmd:\"DRY-RUN\"", &[]);
+        let command = hits[0].concept.command;
+        let span = hits[0].matches.command[0].clone();
+        assert_eq!(&command[span], "dry-run");
+        assert!(hits[0].matches.title.is_empty());
+    }
+}
@@ -0,0 +1,102 @@
+//! Kubernetes-aware query expansion.
+//!
+//! The catalog mixes the short names kubectl accepts (`k create cm`) with the
+//! full resource names used in prose ("Create configmap from file"). Every
+//! spelling in a group is treated as interchangeable, in both directions.
+
+/// Interchangeable spellings of the same resource or tool. The first entry of
+/// each group is the canonical singular form.
+const GROUPS: &[&[&str]] = &[
+    &["kubectl", "k"],
+    &["pod", "pods", "po"],
+    &["service", "services", "svc"],
+    &["deployment", "deployments", "deploy"],
+    &["replicaset", "replicasets", "rs"],
+    &["daemonset", "daemonsets", "ds"],
+    &["statefulset", "statefulsets", "sts"],
+    &["job", "jobs"],
+    &["cronjob", "cronjobs", "cj"],
+    &["configmap", "configmaps", "cm"],
+    &["secret", "secrets"],
+    &["serviceaccount", "serviceaccounts", "sa"],
+    &["persistentvolume", "persistentvolumes", "pv"],
+    &["persistentvolumeclaim", "persistentvolumeclaims", "pvc"],
+    &["storageclass", "storageclasses", "sc"],
+    &["networkpolicy", "networkpolicies", "netpol"],
+    &["ingress", "ingresses", "ing"],
+    &["endpoints", "ep"],
+    &["endpointslice", "endpointslices"],
+    &["namespace", "namespaces", "ns"],
+    &["node", "nodes", "no"],
+    &["event", "events", "ev"],
+    &["role", "roles"],
+    &["rolebinding", "rolebindings"],
+    &["clusterrole", "clusterroles"],
+    &["clusterrolebinding", "clusterrolebindings"],
+    &["resourcequota", "resourcequotas", "quota"],
+    &["limitrange", "limitranges", "limits"],
+    &["horizontalpodautoscaler", "horizontalpodautoscalers", "hpa"],
+    &["poddisruptionbudget", "poddisruptionbudgets", "pdb"],
+    &["customresourcedefinition", "customresourcedefinitions", "crd", "crds"],
+];
+
+/// Returns `term` followed by every other spelling it may stand for: the
+/// members of its synonym group and its naive singular form.
+pub fn expand(term: &str) -> Vec<String> {
+    let mut expanded = vec![term.to_string()];
+    let mut push = |candidate: &str| {
+        if !expanded.iter().any(|existing| existing == candidate) {
+            expanded.push(candidate.to_string());
+        }
+    };
+
+    if let Some(group) = GROUPS.iter().find(|group| group.contains(&term)) {
+        group.iter().for_each(|spelling| push(spelling));
+    }
+
+    // Plural forms not covered by the table, e.g. "probes" or "policies".
+    // Singular to plural needs no rule: the singular is a prefix of it.
+    if term.len() > 4 {
+        if let Some(stem) = term.strip_suffix("ies") {
+            push(&format!("{stem}y"));
+        } else if let Some(stem) = term.strip_suffix('s').filter(|stem| !stem.ends_with('s')) {
+            push(stem);
+        }
+    }
+
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::tests::search;
+
+    #[test]
+    fn a_group_expands_to_all_its_spellings() {
+        assert_eq!(expand("deploy"), ["deploy", "deployment", "deployments"]);
+        assert_eq!(expand("pvc"), ["pvc", "persistentvolumeclaim", "persistentvolumeclaims"]);
+        assert_eq!(expand("k"), ["k", "kubectl"]);
+    }
+
+    #[test]
+    fn plurals_outside_the_table_are_singularized() {
+        assert_eq!(expand("probes"), ["probes", "probe"]);
+        assert_eq!(expand("policies"), ["policies", "policy"]);
+        assert_eq!(expand("address"), ["address"]);
+    }
+
+    #[test]
+    fn unrelated_words_are_left_alone() {
+        assert_eq!(expand("rollback"), ["rollback"]);
+        assert_eq!(expand("yaml"), ["yaml"]);
+    }
+
+    #[test]
+    fn synonyms_widen_a_query() {
+        // Only the titles and descriptions say "deployment".
+        assert_eq!(search("deployments"), ["scale-deployment", "rollback-revision"]);
+        assert_eq!(search("pvc"), ["claim-storage"]);
+        assert_eq!(search("kubectl run"), ["generate-pod-yaml"]);
+    }
+}
@@ -0,0 +1,53 @@
+//! Tokenization shared by the runtime search and by `build.rs`, which
+//! includes this file to build the catalog's inverted index. It must not
+//! depend on anything else in the crate.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// Splits text into lowercase alphanumeric tokens. Punctuation such as `-`,
+/// `=`, `/` and `.` separates tokens, so `--dry-run=client` becomes
+/// `dry`, `run` and `client`.
+pub fn tokenize(text: &str) -> Vec<String> {
+    token_spans(text).map(|(_, token)| token.to_lowercase()).collect()
+}
+
+/// Like `tokenize`, but yields each token as written together with its byte
+/// range in `text`.
+pub fn token_spans(text: &str) -> impl Iterator<Item = (Range<usize>, &str)> {
+    let mut start = None;
+    text.char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+        .filter_map(move |(index, c)| match (c.is_alphanumeric(), start) {
+            (true, None) => {
+                start = Some(index);
+                None
+            }
+            (false, Some(begin)) => {
+                start = None;
+                Some((begin..index, &text[begin..index]))
+            }
+            _ => None,
+        })
+}
+
+/// Builds an inverted index from `(concept, field slot, text)` triples:
+/// every distinct token maps to the sorted, de-duplicated list of
+/// `(concept, field slot)` pairs it occurs in.
+pub fn invert<'a>(
+    documents: impl IntoIterator<Item = (u32, u8, &'a str)>,
+) -> BTreeMap<String, Vec<(u32, u8)>> {
+    let mut index: BTreeMap<String, Vec<(u32, u8)>> = BTreeMap::new();
+
+    for (concept, slot, text) in documents {
+        for token in tokenize(text) {
+            index.entry(token).or_default().push((concept, slot));
+        }
+    }
+    for postings in index.values_mut() {
+        postings.sort_unstable();
+        postings.dedup();
+    }
+
+    index
+}
@@ -0,0 +1,243 @@
+use crate::placeholders::{occurrences, Slot};
+use leptos::*;
+use std::collections::HashMap;
+
+/// A value set once in the context panel and applied to every command.
+pub struct SessionVariable {
+    /// Key of the value in `PlaceholderValues`. Commands may also declare it
+    /// as a placeholder.
+    pub name: &'static str,
+    pub label: &'static str,
+    pub example: &'static str,
+    /// Placeholders the variable fills when they have no value of their own.
+    pub fills: &'static [&'static str],
+    /// Sample values hardcoded in the catalog that the variable replaces.
+    pub stand_ins: &'static [&'static str],
+}
+
+pub const SESSION_VARIABLES: &[SessionVariable] = &[
+    SessionVariable {
+        name: "NAMESPACE",
+        label: "Namespace",
+        example: "e.g. neptune",
+        fills: &[],
+        stand_ins: &[],
+    },
+    SessionVariable {
+        name: "APP_NAME",
+        label: "App name",
+        example: "e.g. api-deploy",
+        fills: &["DEPLOY_NAME", "NAME"],
+        stand_ins: &["myapp", "random-deployment"],
+    },
+    SessionVariable {
+        name: "IMAGE",
+        label: "Image",
+        example: "e.g. httpd:2.4.58",
+        fills: &[],
+        stand_ins: &["nginx:alpine"],
+    },
+    SessionVariable {
+        name: "LABELS",
+        label: "Labels",
+        example: "e.g. app=api,tier=backend",
+        fills: &[],
+        stand_ins: &["app=frontend", "id=awesome-pod", "id=awesome-deployment"],
+    },
+];
+
+/// Slot name of a `LABELS` stand-in given as arguments, as in
+/// `k label pod web app=api`, rather than as the value of `--labels` or `-l`.
+/// Arguments take one `key=value` pair each, so the commas become spaces.
+const LABEL_ARGUMENTS: &str = "LABELS arguments";
+
+/// The value of a slot named `name`, given the values filled in so far: its
+/// own value, or else that of the session variable covering it.
+fn resolve(name: &str, value_of: impl Fn(&str) -> Option<String>) -> Option<String> {
+    if name == LABEL_ARGUMENTS {
+        return value_of("LABELS").map(|labels| labels.split(',').map(str::trim).collect::<Vec<_>>().join(" "));
+    }
+    value_of(name).or_else(|| {
+        SESSION_VARIABLES
+            .iter()
+            .find(|variable| variable.fills.contains(&name))
+            .and_then(|variable| value_of(variable.name))
+    })
+}
+
+/// Slots for the sample values in `command` replaced by the session
+/// variables that `is_set`. Stand-ins match as whole words, where `-`, `/`
+/// and `:` separate words, so `myapp` is replaced in `deploy/myapp` and in
+/// the derived name `myapp-svc`, but not in `myapps`.
+fn stand_in_slots(command: &str, is_set: impl Fn(&str) -> bool) -> Vec<Slot<'static>> {
+    SESSION_VARIABLES
+        .iter()
+        .filter(|variable| is_set(variable.name))
+        .flat_map(|variable| {
+            variable.stand_ins.iter().flat_map(move |stand_in| {
+                occurrences(command, stand_in).into_iter().map(move |range| {
+                    let name = match variable.name {
+                        "LABELS" if is_argument(command, range.start) => LABEL_ARGUMENTS,
+                        name => name,
+                    };
+                    Slot { range, name }
+                })
+            })
+        })
+        .collect()
+}
+
+/// Whether the word starting at `start` is an argument of its own, not the
+/// value of a flag such as `--labels=` or `-l`.
+fn is_argument(command: &str, start: usize) -> bool {
+    let before = &command[..start];
+    before.ends_with(char::is_whitespace)
+        && !before.split_whitespace().next_back().is_some_and(|previous| previous.starts_with('-'))
+}
+
+/// `sessionStorage` key prefix of placeholder values.
+const STORAGE_PREFIX: &str = "placeholder:";
+
+/// Values the reader filled into command placeholders, keyed by placeholder
+/// name. They are shared by every card, so a namespace typed once fills all
+/// `NAMESPACE` placeholders, and kept in `sessionStorage` so they survive a
+/// reload until the tab is closed.
+#[derive(Clone, Copy)]
+pub struct PlaceholderValues(RwSignal<HashMap<String, String>>);
+
+impl PlaceholderValues {
+    /// Creates the shared values, restored from `sessionStorage`, and makes
+    /// them available to child components.
+    pub fn provide() -> PlaceholderValues {
+        let mut values = HashMap::new();
+        if let Some(storage) = session_storage() {
+            let length = storage.length().unwrap_or(0);
+            for index in 0..length {
+                let Ok(Some(key)) = storage.key(index) else {
+                    continue;
+                };
+                if let (Some(name), Ok(Some(value))) = (key.strip_prefix(STORAGE_PREFIX), storage.get_item(&key)) {
+                    values.insert(name.to_string(), value);
+                }
+            }
+        }
+
+        let values = PlaceholderValues(create_rw_signal(values));
+        provide_context(values);
+        values
+    }
+
+    pub fn expect() -> PlaceholderValues {
+        expect_context::<PlaceholderValues>()
+    }
+
+    /// The value of placeholder `name`, or `None` when it has not been filled.
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.0.with(|values| values.get(name).filter(|value| !value.is_empty()).cloned())
+    }
+
+    /// The value a slot named `name` is filled with: its own value, or else
+    /// that of the session variable covering it.
+    pub fn resolve(&self, name: &str) -> Option<String> {
+        resolve(name, |name| self.get(name))
+    }
+
+    /// Slots for the hardcoded sample values in `command` that a session
+    /// variable currently replaces. Each slot is named after its variable.
+    pub fn stand_in_slots(&self, command: &str) -> Vec<Slot<'static>> {
+        stand_in_slots(command, |name| self.get(name).is_some())
+    }
+
+    pub fn set(&self, name: &str, value: String) {
+        if let Some(storage) = session_storage() {
+            let key = format!("{STORAGE_PREFIX}{name}");
+            let _ = if value.is_empty() {
+                storage.remove_item(&key)
+            } else {
+                storage.set_item(&key, &value)
+            };
+        }
+        self.0.update(|values| {
+            values.insert(name.to_string(), value);
+        });
+    }
+}
+
+fn session_storage() -> Option<web_sys::Storage> {
+    window().session_storage().ok().flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::placeholders::{fill, merge, slots};
+
+    const VALUES: &[(&str, &str)] = &[
+        ("NAMESPACE", "neptune"),
+        ("APP_NAME", "api"),
+        ("IMAGE", "httpd:2.4"),
+        ("LABELS", "app=api,tier=backend"),
+    ];
+
+    fn value_of(name: &str) -> Option<String> {
+        VALUES.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+    }
+
+    /// `command` as a card renders it with `VALUES` in the context panel.
+    fn render(command: &str, placeholders: &[&'static str]) -> String {
+        let mut all = slots(command, placeholders);
+        all.extend(stand_in_slots(command, |name| value_of(name).is_some()));
+        fill(command, &merge(all), |name| resolve(name, value_of))
+    }
+
+    #[test]
+    fn placeholders_take_their_own_value_or_their_variables() {
+        assert_eq!(resolve("NAMESPACE", value_of), Some("neptune".to_string()));
+        assert_eq!(resolve("DEPLOY_NAME", value_of), Some("api".to_string()));
+        assert_eq!(resolve("POD_NAME", value_of), None);
+
+        let own_value = |name: &str| if name == "DEPLOY_NAME" { Some("web".to_string()) } else { value_of(name) };
+        assert_eq!(resolve("DEPLOY_NAME", own_value), Some("web".to_string()));
+    }
+
+    #[test]
+    fn placeholders_and_stand_ins_are_filled() {
+        assert_eq!(
+            render("k set image deploy/myapp nginx=nginx:alpine -n NAMESPACE", &["NAMESPACE"]),
+            "k set image deploy/api nginx=httpd:2.4 -n neptune"
+        );
+        assert_eq!(
+            render("k scale deploy DEPLOY_NAME --replicas=3", &["DEPLOY_NAME"]),
+            "k scale deploy api --replicas=3"
+        );
+    }
+
+    #[test]
+    fn stand_ins_are_replaced_as_whole_words() {
+        assert_eq!(render("k expose deploy myapp --name=myapp-svc", &[]), "k expose deploy api --name=api-svc");
+        assert_eq!(render("k get myapps my_myapp", &[]), "k get myapps my_myapp");
+        assert_eq!(render("k create deploy random-deployment", &[]), "k create deploy api");
+    }
+
+    #[test]
+    fn labels_are_comma_separated_in_flags_and_space_separated_as_arguments() {
+        assert_eq!(
+            render("k run web --image=nginx:alpine --labels=app=frontend", &[]),
+            "k run web --image=httpd:2.4 --labels=app=api,tier=backend"
+        );
+        assert_eq!(render("k get pod -l app=frontend", &[]), "k get pod -l app=api,tier=backend");
+        assert_eq!(
+            render("k label deployment random-deployment id=awesome-deployment", &[]),
+            "k label deployment api app=api tier=backend"
+        );
+    }
+
+    #[test]
+    fn unset_variables_leave_their_stand_ins() {
+        let command = "k run web --image=nginx:alpine --labels=app=frontend";
+        assert!(stand_in_slots(command, |_| false).is_empty());
+        let labels_only = stand_in_slots(command, |name| name == "LABELS");
+        let start = command.find("app=frontend").unwrap();
+        assert_eq!(labels_only, [Slot { range: start..start + "app=frontend".len(), name: "LABELS" }]);
+    }
+}
@@ -0,0 +1,253 @@
+//! Splitting shell-like commands into words, as the typing drill and the
+//! command linter see them. Only what the catalog's commands use is
+//! understood: quotes, backslash escapes and line continuations, comments,
+//! and the operators that chain invocations. Expansions are left as written.
+
+/// Operators that separate one invocation from the next. An unquoted line
+/// break counts as `;`.
+const OPERATORS: &[&str] = &["&&", "||", "|", ";", ">>", ">", "<", "&"];
+
+/// A shell word, or an operator between invocations.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Word {
+    Text(String),
+    Operator(&'static str),
+}
+
+/// A word with the 1-based line it starts on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Token {
+    pub word: Word,
+    pub line: usize,
+}
+
+/// Splits `text` into shell words, removing quotes and comments.
+pub fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    // The line `current` started on, once it is a word, even an empty one
+    // such as `""`.
+    let mut word_line = None;
+    let mut line = 1;
+    let mut chars = text.char_indices().peekable();
+
+    let finish = |tokens: &mut Vec<Token>, current: &mut String, word_line: &mut Option<usize>| {
+        if let Some(line) = word_line.take() {
+            tokens.push(Token { word: Word::Text(std::mem::take(current)), line });
+        }
+    };
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\'' => {
+                word_line.get_or_insert(line);
+                for (_, c) in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    line += usize::from(c == '\n');
+                    current.push(c);
+                }
+            }
+            '"' => {
+                word_line.get_or_insert(line);
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' if chars.peek().is_some_and(|&(_, next)| matches!(next, '"' | '\\' | '$' | '`')) => {
+                            current.push(chars.next().unwrap().1);
+                        }
+                        _ => {
+                            line += usize::from(c == '\n');
+                            current.push(c);
+                        }
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                // A line continuation.
+                Some((_, '\n')) => line += 1,
+                Some((_, next)) => {
+                    word_line.get_or_insert(line);
+                    current.push(next);
+                }
+                None => {}
+            },
+            '#' if word_line.is_none() => {
+                while chars.peek().is_some_and(|&(_, next)| next != '\n') {
+                    chars.next();
+                }
+            }
+            '\n' => {
+                finish(&mut tokens, &mut current, &mut word_line);
+                tokens.push(Token { word: Word::Operator(";"), line });
+                line += 1;
+            }
+            c if c.is_whitespace() => finish(&mut tokens, &mut current, &mut word_line),
+            _ => {
+                // The redirections in `2>&1` stay part of the word.
+                let is_redirection = word_line.is_some()
+                    && (matches!(c, '>' | '<') && current.chars().all(|c| c.is_ascii_digit())
+                        || c == '&' && current.ends_with(['>', '<']));
+                let operator = OPERATORS
+                    .iter()
+                    .find(|operator| text[index..].starts_with(**operator))
+                    .filter(|_| !is_redirection);
+                match operator {
+                    Some(operator) => {
+                        finish(&mut tokens, &mut current, &mut word_line);
+                        for _ in 1..operator.len() {
+                            chars.next();
+                        }
+                        tokens.push(Token { word: Word::Operator(operator), line });
+                    }
+                    _ => {
+                        word_line.get_or_insert(line);
+                        current.push(c);
+                    }
+                }
+            }
+        }
+    }
+    finish(&mut tokens, &mut current, &mut word_line);
+
+    // Drop empty statements left by blank lines and comments.
+    let mut cleaned: Vec<Token> = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let after_separator = matches!(cleaned.last(), None | Some(Token { word: Word::Operator(";"), .. }));
+        if token.word == Word::Operator(";") && after_separator {
+            continue;
+        }
+        cleaned.push(token);
+    }
+    if cleaned.last().is_some_and(|token| token.word == Word::Operator(";")) {
+        cleaned.pop();
+    }
+    cleaned
+}
+
+/// Like `tokenize`, without the lines.
+pub fn split_words(text: &str) -> Vec<Word> {
+    tokenize(text).into_iter().map(|token| token.word).collect()
+}
+
+/// Splits a short flag from what is attached to it: `-oyaml` into `-o` and
+/// `yaml`, and `-i` into `-i` and nothing.
+pub fn split_short_flag(word: &str) -> (&str, &str) {
+    word.split_at(word.char_indices().nth(2).map_or(word.len(), |(index, _)| index))
+}
+
+/// The tool a program name invokes, for the tools whose flags are known:
+/// `k` is the usual alias of `kubectl`, and `podman` takes `docker`'s flags.
+pub fn program_family(program: &str) -> Option<&'static str> {
+    match program {
+        "k" | "kubectl" => Some("kubectl"),
+        "helm" => Some("helm"),
+        "docker" | "podman" => Some("docker"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(word: &str) -> Word {
+        Word::Text(word.to_string())
+    }
+
+    fn texts(words: &[&str]) -> Vec<Word> {
+        words.iter().map(|word| text(word)).collect()
+    }
+
+    #[test]
+    fn quotes_are_removed() {
+        assert_eq!(
+            split_words(r#"k exec web -- sh -c 'echo "$HOME"' "a b" """#),
+            texts(&["k", "exec", "web", "--", "sh", "-c", r#"echo "$HOME""#, "a b", ""])
+        );
+        assert_eq!(split_words(r#"--from-literal=pass="s3 cr3t""#), texts(&["--from-literal=pass=s3 cr3t"]));
+        assert_eq!(split_words("'a | b; c'"), texts(&["a | b; c"]));
+    }
+
+    #[test]
+    fn backslashes_escape_the_next_character() {
+        assert_eq!(split_words(r"echo a\ b \| \#c"), texts(&["echo", "a b", "|", "#c"]));
+        // In double quotes only `"`, `\`, `$` and backquotes are escaped.
+        assert_eq!(split_words(r#"echo "\"\$x\n""#), texts(&["echo", r#""$x\n"#]));
+        // Single quotes keep backslashes.
+        assert_eq!(split_words(r"echo '\n'"), texts(&["echo", r"\n"]));
+    }
+
+    #[test]
+    fn operators_separate_invocations() {
+        assert_eq!(
+            split_words("k get pods|grep web && echo ok || true > out.txt 2>&1"),
+            vec![
+                text("k"),
+                text("get"),
+                text("pods"),
+                Word::Operator("|"),
+                text("grep"),
+                text("web"),
+                Word::Operator("&&"),
+                text("echo"),
+                text("ok"),
+                Word::Operator("||"),
+                text("true"),
+                Word::Operator(">"),
+                text("out.txt"),
+                text("2>&1"),
+            ]
+        );
+        assert_eq!(
+            split_words("k logs web >> log &"),
+            [text("k"), text("logs"), text("web"), Word::Operator(">>"), text("log"), Word::Operator("&")]
+        );
+    }
+
+    #[test]
+    fn line_continuations_join_lines() {
+        let tokens = tokenize("k create deploy web \\\n  --image=nginx \\\n  --replicas=2");
+        let words: Vec<_> = tokens.iter().map(|token| token.word.clone()).collect();
+        assert_eq!(words, texts(&["k", "create", "deploy", "web", "--image=nginx", "--replicas=2"]));
+        let lines: Vec<_> = tokens.iter().map(|token| token.line).collect();
+        assert_eq!(lines, [1, 1, 1, 1, 2, 3]);
+    }
+
+    #[test]
+    fn line_breaks_end_statements_and_comments_are_dropped() {
+        let tokens = tokenize("# scale it\nk scale deploy web --replicas=3 # now\n\nk get deploy#web\n");
+        let words: Vec<_> = tokens.iter().map(|token| (token.word.clone(), token.line)).collect();
+        assert_eq!(
+            words,
+            [
+                (text("k"), 2),
+                (text("scale"), 2),
+                (text("deploy"), 2),
+                (text("web"), 2),
+                (text("--replicas=3"), 2),
+                (Word::Operator(";"), 2),
+                (text("k"), 4),
+                (text("get"), 4),
+                (text("deploy#web"), 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn short_flags_split_after_their_letter() {
+        assert_eq!(split_short_flag("-oyaml"), ("-o", "yaml"));
+        assert_eq!(split_short_flag("-i"), ("-i", ""));
+        assert_eq!(split_short_flag("-é"), ("-é", ""));
+        assert_eq!(split_short_flag("-éx"), ("-é", "x"));
+    }
+
+    #[test]
+    fn known_programs_have_a_family() {
+        assert_eq!(program_family("k"), Some("kubectl"));
+        assert_eq!(program_family("kubectl"), Some("kubectl"));
+        assert_eq!(program_family("podman"), Some("docker"));
+        assert_eq!(program_family("grep"), None);
+    }
+}
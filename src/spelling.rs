@@ -0,0 +1,40 @@
+//! "Did you mean" suggestions for misspelled names, such as flags or
+//! manifest fields.
+
+use crate::search::edit_distance;
+
+/// The candidate closest to `word`, if it is close enough to be a likely
+/// misspelling: at most one edit per three characters, where swapping two
+/// adjacent characters counts as one. Ties go to the earlier candidate.
+pub fn closest<'a>(word: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let allowed = (word.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .filter_map(|candidate| Some((edit_distance(word, candidate, allowed)?, candidate)))
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closest_allows_one_edit_per_three_characters() {
+        assert_eq!(closest("replicas", ["replicas"]), Some("replicas"));
+        assert_eq!(closest("--imag", ["--image"]), Some("--image"));
+        assert_eq!(closest("restartpolicy", ["restartPolicy"]), Some("restartPolicy"));
+        assert_eq!(closest("--raw", ["--rwa"]), Some("--rwa"));
+        assert_eq!(closest("port", ["ports", "pod"]), Some("ports"));
+        assert_eq!(closest("ab", ["abc", "xyz"]), Some("abc"));
+        assert_eq!(closest("replicas", ["replicaset"]), Some("replicaset"));
+        assert_eq!(closest("replicas", ["rep"]), None);
+    }
+
+    #[test]
+    fn closest_ignores_unrelated_names() {
+        let fields = ["initialDelaySeconds", "periodSeconds", "timeoutSeconds"];
+        assert_eq!(closest("initialDelaySecond", fields), Some("initialDelaySeconds"));
+        assert_eq!(closest("image", fields), None);
+    }
+}
@@ -0,0 +1,209 @@
+//! Spaced repetition scheduling after SuperMemo-2 (SM-2).
+//!
+//! Each card carries an ease factor and an interval. A successful recall
+//! multiplies the interval by the ease and nudges the ease up or down by how
+//! hard the recall was; a failed recall starts the card over. Days are
+//! counted as whole days since the Unix epoch, so this module needs no clock
+//! and does not depend on the UI.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A calendar day, counted from 1970-01-01.
+pub type Day = i64;
+
+/// Ease factor of a card that has never been reviewed.
+pub const INITIAL_EASE: f64 = 2.5;
+
+/// SM-2 never lets the ease factor drop below this.
+pub const MINIMUM_EASE: f64 = 1.3;
+
+/// How well the reader recalled a card.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Grade {
+    /// Not recalled; the card starts over.
+    Again,
+    /// Recalled with serious difficulty.
+    Hard,
+    /// Recalled after some hesitation.
+    Good,
+    /// Recalled instantly.
+    Easy,
+}
+
+impl Grade {
+    pub const ALL: [Grade; 4] = [Grade::Again, Grade::Hard, Grade::Good, Grade::Easy];
+
+    /// The SM-2 response quality, from 0 (blackout) to 5 (perfect).
+    pub fn quality(self) -> u8 {
+        match self {
+            Grade::Again => 1,
+            Grade::Hard => 3,
+            Grade::Good => 4,
+            Grade::Easy => 5,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Grade::Again => "Again",
+            Grade::Hard => "Hard",
+            Grade::Good => "Good",
+            Grade::Easy => "Easy",
+        }
+    }
+}
+
+/// The scheduling state of one card.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReviewState {
+    /// Successful reviews in a row.
+    pub repetitions: u32,
+    pub interval_days: u32,
+    pub ease: f64,
+    /// The day the card is next due.
+    pub due: Day,
+}
+
+impl Default for ReviewState {
+    /// A card that has never been reviewed, due at once.
+    fn default() -> ReviewState {
+        ReviewState {
+            repetitions: 0,
+            interval_days: 0,
+            ease: INITIAL_EASE,
+            due: Day::MIN,
+        }
+    }
+}
+
+impl ReviewState {
+    /// The state after a review graded `grade` on `today`.
+    pub fn review(&self, grade: Grade, today: Day) -> ReviewState {
+        let quality = f64::from(grade.quality());
+        let (repetitions, interval_days) = if grade.quality() < 3 {
+            (0, 1)
+        } else {
+            let interval = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (f64::from(self.interval_days) * self.ease).round() as u32,
+            };
+            (self.repetitions + 1, interval)
+        };
+
+        let penalty = 5.0 - quality;
+        let ease = (self.ease + 0.1 - penalty * (0.08 + penalty * 0.02)).max(MINIMUM_EASE);
+
+        ReviewState {
+            repetitions,
+            interval_days,
+            ease,
+            due: today + Day::from(interval_days),
+        }
+    }
+
+    pub fn is_due(&self, today: Day) -> bool {
+        self.due <= today
+    }
+}
+
+/// The cards to study on `today`, out of `ids` in catalog order: cards already
+/// reviewed that are due, most overdue first, then up to `new_limit` cards that
+/// were never reviewed.
+pub fn due_queue<'a>(
+    ids: impl IntoIterator<Item = &'a str>,
+    states: &BTreeMap<String, ReviewState>,
+    today: Day,
+    new_limit: usize,
+) -> Vec<&'a str> {
+    let mut due = Vec::new();
+    let mut new = Vec::new();
+    for id in ids {
+        match states.get(id) {
+            Some(state) if state.is_due(today) => due.push((state.due, id)),
+            Some(_) => {}
+            None => new.push(id),
+        }
+    }
+    // A stable sort keeps catalog order among cards due the same day.
+    due.sort_by_key(|&(day, _)| day);
+
+    due.into_iter()
+        .map(|(_, id)| id)
+        .chain(new.into_iter().take(new_limit))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TODAY: Day = 20_000;
+
+    fn reviewed(grades: &[Grade]) -> ReviewState {
+        grades
+            .iter()
+            .fold(ReviewState::default(), |state, &grade| state.review(grade, TODAY))
+    }
+
+    #[test]
+    fn first_successful_reviews_use_fixed_intervals() {
+        assert_eq!(reviewed(&[Grade::Good]).interval_days, 1);
+        assert_eq!(reviewed(&[Grade::Good, Grade::Good]).interval_days, 6);
+    }
+
+    #[test]
+    fn later_intervals_grow_by_the_ease_factor() {
+        let state = reviewed(&[Grade::Good, Grade::Good]);
+        let next = state.review(Grade::Good, TODAY);
+        assert_eq!(next.interval_days, (6.0 * state.ease).round() as u32);
+        assert_eq!(next.repetitions, 3);
+        assert_eq!(next.due, TODAY + Day::from(next.interval_days));
+    }
+
+    #[test]
+    fn good_keeps_the_ease_and_easy_raises_it() {
+        assert!((reviewed(&[Grade::Good]).ease - INITIAL_EASE).abs() < 1e-9);
+        assert!((reviewed(&[Grade::Easy]).ease - (INITIAL_EASE + 0.1)).abs() < 1e-9);
+        assert!((reviewed(&[Grade::Hard]).ease - (INITIAL_EASE - 0.14)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn failed_recall_starts_the_card_over() {
+        let state = reviewed(&[Grade::Good, Grade::Good, Grade::Good, Grade::Again]);
+        assert_eq!(state.repetitions, 0);
+        assert_eq!(state.interval_days, 1);
+        assert_eq!(state.due, TODAY + 1);
+        assert!(state.ease < INITIAL_EASE);
+    }
+
+    #[test]
+    fn ease_never_drops_below_the_minimum() {
+        let state = reviewed(&[Grade::Again; 20]);
+        assert!((state.ease - MINIMUM_EASE).abs() < 1e-9);
+    }
+
+    #[test]
+    fn new_cards_are_due_at_once() {
+        assert!(ReviewState::default().is_due(TODAY));
+        assert!(!reviewed(&[Grade::Good]).is_due(TODAY));
+        assert!(reviewed(&[Grade::Good]).is_due(TODAY + 1));
+    }
+
+    #[test]
+    fn queue_serves_overdue_cards_first_then_limited_new_cards() {
+        let mut states = BTreeMap::new();
+        let state = |due| ReviewState { due, ..ReviewState::default() };
+        states.insert("due-today".to_string(), state(TODAY));
+        states.insert("overdue".to_string(), state(TODAY - 3));
+        states.insert("not-yet".to_string(), state(TODAY + 2));
+
+        let ids = ["new-a", "due-today", "not-yet", "new-b", "overdue", "new-c"];
+        assert_eq!(
+            due_queue(ids, &states, TODAY, 2),
+            ["overdue", "due-today", "new-a", "new-b"]
+        );
+        assert_eq!(due_queue(ids, &states, TODAY, 0), ["overdue", "due-today"]);
+    }
+}
@@ -0,0 +1,32 @@
+//! JSON values kept in `localStorage`, which outlive the browser session.
+//! Reading falls back to `None` when storage is unavailable (private mode,
+//! disabled by policy) or the stored value no longer parses, and writing
+//! failures are ignored: the app keeps working, it just forgets.
+
+use leptos::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Prefix of every key this app writes, so it never collides with other
+/// apps served from the same origin.
+const KEY_PREFIX: &str = "ckad-wiki:";
+
+fn local_storage() -> Option<web_sys::Storage> {
+    window().local_storage().ok().flatten()
+}
+
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    serde_json::from_str(&load_text(key)?).ok()
+}
+
+/// The stored text itself, for values that must not be dropped when they
+/// fail to parse.
+pub fn load_text(key: &str) -> Option<String> {
+    local_storage()?.get_item(&format!("{KEY_PREFIX}{key}")).ok()?
+}
+
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    if let (Some(storage), Ok(text)) = (local_storage(), serde_json::to_string(value)) {
+        let _ = storage.set_item(&format!("{KEY_PREFIX}{key}"), &text);
+    }
+}
@@ -0,0 +1,79 @@
+//! Conversion between YAML and JSON values, so that YAML input and output can
+//! go through the same serde types as JSON.
+
+use serde_json::{Map, Number, Value};
+use yaml_rust2::yaml::Hash;
+use yaml_rust2::{Yaml, YamlEmitter, YamlLoader};
+
+/// Parses every document of a YAML stream into JSON values.
+pub fn parse_documents(text: &str) -> Result<Vec<Value>, String> {
+    let documents = YamlLoader::load_from_str(text).map_err(|err| err.to_string())?;
+    documents.iter().map(to_json).collect()
+}
+
+/// Converts a YAML value to JSON. Mapping keys must be scalars; aliases
+/// cannot be represented.
+pub fn to_json(yaml: &Yaml) -> Result<Value, String> {
+    Ok(match yaml {
+        Yaml::Null => Value::Null,
+        Yaml::Boolean(value) => Value::Bool(*value),
+        Yaml::Integer(value) => Value::from(*value),
+        Yaml::Real(text) => text
+            .parse::<f64>()
+            .ok()
+            .and_then(Number::from_f64)
+            .map_or_else(|| Value::String(text.clone()), Value::Number),
+        Yaml::String(text) => Value::String(text.clone()),
+        Yaml::Array(items) => Value::Array(items.iter().map(to_json).collect::<Result<_, _>>()?),
+        Yaml::Hash(entries) => {
+            let mut map = Map::new();
+            for (key, value) in entries {
+                let key = match key {
+                    Yaml::String(text) | Yaml::Real(text) => text.clone(),
+                    Yaml::Integer(number) => number.to_string(),
+                    Yaml::Boolean(flag) => flag.to_string(),
+                    Yaml::Null => "null".to_string(),
+                    _ => return Err("mapping keys must be plain values".to_string()),
+                };
+                map.insert(key, to_json(value)?);
+            }
+            Value::Object(map)
+        }
+        Yaml::Alias(_) => return Err("YAML aliases are not supported".to_string()),
+        Yaml::BadValue => return Err("invalid YAML value".to_string()),
+    })
+}
+
+/// Converts a JSON value to YAML.
+pub fn from_json(value: &Value) -> Yaml {
+    match value {
+        Value::Null => Yaml::Null,
+        Value::Bool(flag) => Yaml::Boolean(*flag),
+        Value::Number(number) => match number.as_i64() {
+            Some(integer) => Yaml::Integer(integer),
+            None => Yaml::Real(number.to_string()),
+        },
+        Value::String(text) => Yaml::String(text.clone()),
+        Value::Array(items) => Yaml::Array(items.iter().map(from_json).collect()),
+        Value::Object(map) => {
+            let mut hash = Hash::new();
+            for (key, value) in map {
+                hash.insert(Yaml::String(key.clone()), from_json(value));
+            }
+            Yaml::Hash(hash)
+        }
+    }
+}
+
+/// Renders a JSON value as a YAML document, with multi-line strings as
+/// literal blocks so commands stay readable.
+pub fn emit(value: &Value) -> String {
+    let mut out = String::new();
+    let mut emitter = YamlEmitter::new(&mut out);
+    emitter.multiline_strings(true);
+    if emitter.dump(&from_json(value)).is_err() {
+        return String::new();
+    }
+    out.push('\n');
+    out
+}
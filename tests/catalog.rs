@@ -0,0 +1,79 @@
+//! Checks over the whole built-in catalog that `build.rs` cannot do without
+//! a YAML parser and the Kubernetes schema.
+
+use ckad_wiki::ckad_data::{catalog_concepts, ConceptKind};
+use ckad_wiki::lint::lint_command;
+use ckad_wiki::manifest::{is_full_manifest, parse, validate_snippet, Value};
+
+/// Complete manifests are checked against the schema of their kind, and
+/// fragments against their declared `fragment` type.
+#[test]
+fn every_yaml_snippet_is_valid() {
+    let failures: Vec<String> = catalog_concepts()
+        .filter(|(_, concept)| concept.kind == ConceptKind::Yaml)
+        .flat_map(|(_, concept)| {
+            validate_snippet(concept.command, concept.fragment).into_iter().map(move |problem| {
+                format!("{} (line {}): {}", concept.title, problem.line, problem.message)
+            })
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "invalid YAML snippets:\n{}", failures.join("\n"));
+}
+
+/// A fragment without a declared type would only be checked for syntax.
+#[test]
+fn yaml_fragments_declare_their_type() {
+    let failures: Vec<&str> = catalog_concepts()
+        .filter(|(_, concept)| concept.kind == ConceptKind::Yaml && concept.fragment.is_none())
+        .filter(|(_, concept)| {
+            parse(concept.command).is_ok_and(|documents| {
+                documents.iter().any(|document| document.value != Value::Null && !is_full_manifest(document))
+            })
+        })
+        .map(|(_, concept)| concept.title)
+        .collect();
+
+    assert!(failures.is_empty(), "YAML fragments without a `fragment` type:\n{}", failures.join("\n"));
+}
+
+/// Shell commands, and the other spellings the typing drill accepts, only
+/// use subcommands and flags that kubectl, helm, docker and podman know, in
+/// their current form.
+#[test]
+fn shell_commands_pass_the_linter() {
+    let failures: Vec<String> = catalog_concepts()
+        .filter(|(_, concept)| concept.kind == ConceptKind::Shell)
+        .flat_map(|(_, concept)| {
+            std::iter::once(concept.command).chain(concept.equivalents.iter().copied()).flat_map(move |command| {
+                lint_command(command).into_iter().map(move |problem| {
+                    format!("{} (line {}): {}", concept.title, problem.line, problem.message)
+                })
+            })
+        })
+        .collect();
+
+    assert!(failures.is_empty(), "commands the linter rejects:\n{}", failures.join("\n"));
+}
+
+/// A shell command that parses as a YAML mapping of single-word keys was
+/// most likely misclassified and should declare `kind = "yaml"`. Commands
+/// such as `k describe pod | grep status:` also parse as a mapping, but
+/// their key is the whole command line.
+#[test]
+fn shell_commands_are_not_yaml() {
+    let failures: Vec<&str> = catalog_concepts()
+        .filter(|(_, concept)| concept.kind == ConceptKind::Shell)
+        .filter(|(_, concept)| {
+            parse(concept.command).is_ok_and(|documents| {
+                documents.iter().any(|document| match &document.value {
+                    Value::Map(entries) => entries.iter().all(|entry| !entry.key.contains(char::is_whitespace)),
+                    _ => false,
+                })
+            })
+        })
+        .map(|(_, concept)| concept.title)
+        .collect();
+
+    assert!(failures.is_empty(), "shell commands that look like YAML:\n{}", failures.join("\n"));
+}